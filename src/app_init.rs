@@ -1,4 +1,7 @@
-use crate::{gui::setup_font, gui::show_error_dialog};
+use crate::{
+    gui::setup_font,
+    gui::{ErrorReport, show_error_dialog, show_error_report},
+};
 use dirs;
 use fltk::{app, enums::Font};
 use std::{
@@ -36,17 +39,27 @@ pub fn get_game_directory() -> PathBuf {
     path
 }
 
-pub fn setup_error_handler() -> (Arc<Mutex<Option<String>>>, Font) {
-    let error_message = Arc::new(Mutex::new(None::<String>));
+pub fn get_libraries_directory() -> PathBuf {
+    let path = dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("minecraft_launcher")
+        .join("cache");
+
+    let _ = std::fs::create_dir_all(&path);
+    path
+}
+
+pub fn setup_error_handler() -> (Arc<Mutex<Option<ErrorReport>>>, Font) {
+    let error_message = Arc::new(Mutex::new(None::<ErrorReport>));
     let error_for_awake = error_message.clone();
 
     let font_for_error = setup_font(app::App::default());
     app::add_idle3(move |_| {
         let mut error = error_for_awake.lock().unwrap();
-        if let Some(msg) = error.take() {
+        if let Some(report) = error.take() {
             let font_clone = font_for_error;
             app::add_timeout3(0.1, move |_| {
-                show_error_dialog(&msg, font_clone);
+                show_error_report(&report, font_clone);
             });
         }
     });
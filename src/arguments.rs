@@ -0,0 +1,250 @@
+use crate::models::{Argument, Rule, VersionData};
+use std::collections::HashMap;
+
+const LEGACY_JVM_ARGUMENTS: [&str; 3] = [
+    "-Djava.library.path=${natives_directory}",
+    "-cp",
+    "${classpath}",
+];
+
+const DEFAULT_GAME_ARGUMENTS: &str = "--username ${auth_player_name} --version ${version_name} \
+     --gameDir ${game_directory} --assetsDir ${assets_root} --assetIndex ${assets_index_name} \
+     --uuid ${auth_uuid} --accessToken ${auth_access_token} --userProperties ${user_properties}";
+
+pub struct LaunchArguments {
+    pub jvm: Vec<String>,
+    pub game: Vec<String>,
+}
+
+pub fn build_arguments(
+    version_data: &VersionData,
+    placeholders: &HashMap<&str, String>,
+    features: &[&str],
+) -> LaunchArguments {
+    let (jvm, game) = match &version_data.arguments {
+        Some(arguments) => (
+            collect_arguments(&arguments.jvm, placeholders, features),
+            collect_arguments(&arguments.game, placeholders, features),
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let jvm = if jvm.is_empty() {
+        substitute_all(LEGACY_JVM_ARGUMENTS.iter().copied(), placeholders)
+    } else {
+        jvm
+    };
+
+    let game = if game.is_empty() {
+        let game_arguments = version_data
+            .minecraft_arguments
+            .as_deref()
+            .unwrap_or(DEFAULT_GAME_ARGUMENTS);
+        substitute_all(game_arguments.split_whitespace(), placeholders)
+    } else {
+        game
+    };
+
+    LaunchArguments { jvm, game }
+}
+
+fn collect_arguments(
+    arguments: &[Argument],
+    placeholders: &HashMap<&str, String>,
+    features: &[&str],
+) -> Vec<String> {
+    let mut result = Vec::new();
+
+    for argument in arguments {
+        match argument {
+            Argument::Plain(value) => result.push(substitute(value, placeholders)),
+            Argument::Conditional { rules, value } => {
+                if rules_allow(rules, features) {
+                    result.extend(substitute_all(value.values().into_iter(), placeholders));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn substitute_all<'a>(
+    arguments: impl Iterator<Item = &'a str>,
+    placeholders: &HashMap<&str, String>,
+) -> Vec<String> {
+    arguments
+        .map(|argument| substitute(argument, placeholders))
+        .collect()
+}
+
+pub fn substitute(argument: &str, placeholders: &HashMap<&str, String>) -> String {
+    let mut result = String::with_capacity(argument.len());
+    let mut rest = argument;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        match after.find('}') {
+            Some(end) => {
+                let key = &after[..end];
+                match placeholders.get(key) {
+                    Some(value) => result.push_str(value),
+                    None => result.push_str(&rest[start..start + 2 + end + 1]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+pub fn rules_allow(rules: &[Rule], features: &[&str]) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+
+    let mut allow = false;
+
+    for rule in rules {
+        if rule_applies(rule, features) {
+            allow = rule.action == "allow";
+        }
+    }
+
+    allow
+}
+
+fn rule_applies(rule: &Rule, features: &[&str]) -> bool {
+    if let Some(os) = &rule.os {
+        if let Some(name) = &os.name
+            && name != current_os()
+        {
+            return false;
+        }
+
+        if let Some(arch) = &os.arch
+            && arch != current_arch()
+        {
+            return false;
+        }
+    }
+
+    if let Some(required_features) = &rule.features {
+        for (feature, expected) in required_features {
+            if features.contains(&feature.as_str()) != *expected {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+pub fn current_os() -> &'static str {
+    #[cfg(target_os = "windows")]
+    return "windows";
+    #[cfg(target_os = "linux")]
+    return "linux";
+    #[cfg(target_os = "macos")]
+    return "osx";
+}
+
+pub fn current_arch() -> &'static str {
+    if cfg!(target_arch = "x86") {
+        "x86"
+    } else if cfg!(target_arch = "aarch64") {
+        "arm64"
+    } else {
+        "x86_64"
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(json: &str) -> Vec<Rule> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn substitute_replaces_known_placeholders() {
+        let placeholders = HashMap::from([
+            ("auth_player_name", "Steve".to_string()),
+            ("version_name", "1.20.1".to_string()),
+        ]);
+
+        assert_eq!(
+            substitute("--username=${auth_player_name}", &placeholders),
+            "--username=Steve"
+        );
+        assert_eq!(
+            substitute("${auth_player_name}-${version_name}", &placeholders),
+            "Steve-1.20.1"
+        );
+    }
+
+    #[test]
+    fn substitute_keeps_unknown_and_unterminated_placeholders() {
+        let placeholders = HashMap::from([("version_name", "1.20.1".to_string())]);
+
+        assert_eq!(
+            substitute("${unknown} ${version_name}", &placeholders),
+            "${unknown} 1.20.1"
+        );
+        assert_eq!(
+            substitute("${version_name} ${broken", &placeholders),
+            "1.20.1 ${broken"
+        );
+        assert_eq!(substitute("plain", &placeholders), "plain");
+    }
+
+    #[test]
+    fn empty_rules_allow() {
+        assert!(rules_allow(&[], &[]));
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let os_rules = rules(&format!(
+            r#"[{{ "action": "allow" }}, {{ "action": "disallow", "os": {{ "name": "{}" }} }}]"#,
+            current_os()
+        ));
+        assert!(!rules_allow(&os_rules, &[]));
+
+        let other_os_rules = rules(
+            r#"[{ "action": "allow" }, { "action": "disallow", "os": { "name": "nonexistent" } }]"#,
+        );
+        assert!(rules_allow(&other_os_rules, &[]));
+    }
+
+    #[test]
+    fn os_only_rule_disallows_other_systems() {
+        let only_other_os = rules(r#"[{ "action": "allow", "os": { "name": "nonexistent" } }]"#);
+        assert!(!rules_allow(&only_other_os, &[]));
+
+        let other_arch = rules(&format!(
+            r#"[{{ "action": "allow", "os": {{ "name": "{}", "arch": "nonexistent" }} }}]"#,
+            current_os()
+        ));
+        assert!(!rules_allow(&other_arch, &[]));
+    }
+
+    #[test]
+    fn feature_rules_follow_enabled_features() {
+        let demo = rules(r#"[{ "action": "allow", "features": { "is_demo_user": true } }]"#);
+        assert!(!rules_allow(&demo, &[]));
+        assert!(rules_allow(&demo, &["is_demo_user"]));
+
+        let not_demo = rules(r#"[{ "action": "allow", "features": { "is_demo_user": false } }]"#);
+        assert!(rules_allow(&not_demo, &[]));
+        assert!(!rules_allow(&not_demo, &["is_demo_user"]));
+    }
+}
\ No newline at end of file
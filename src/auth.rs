@@ -0,0 +1,357 @@
+use crate::{download_engine::DownloadEngine, models::MicrosoftAccount};
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    error::Error,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
+const TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
+const XBOX_LIVE_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
+const XSTS_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
+const MINECRAFT_LOGIN_URL: &str =
+    "https://api.minecraftservices.com/authentication/login_with_xbox";
+const MINECRAFT_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
+
+const SCOPE: &str = "XboxLive.signin offline_access";
+const REFRESH_MARGIN_SECS: u64 = 300;
+
+#[derive(Clone, Debug)]
+pub struct AuthEndpoints {
+    pub client_id: String,
+    pub device_code_url: String,
+    pub token_url: String,
+    pub xbox_live_url: String,
+    pub xsts_url: String,
+    pub minecraft_login_url: String,
+    pub minecraft_profile_url: String,
+}
+
+impl Default for AuthEndpoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthEndpoints {
+    pub fn new() -> Self {
+        let client_id = std::env::var("MSA_CLIENT_ID")
+            .ok()
+            .or_else(|| option_env!("MSA_CLIENT_ID").map(str::to_string))
+            .unwrap_or_default();
+
+        if let Ok(base_url) = std::env::var("MSA_AUTH_BASE_URL") {
+            return Self::with_base_url(&client_id, &base_url);
+        }
+
+        AuthEndpoints {
+            client_id,
+            device_code_url: DEVICE_CODE_URL.to_string(),
+            token_url: TOKEN_URL.to_string(),
+            xbox_live_url: XBOX_LIVE_URL.to_string(),
+            xsts_url: XSTS_URL.to_string(),
+            minecraft_login_url: MINECRAFT_LOGIN_URL.to_string(),
+            minecraft_profile_url: MINECRAFT_PROFILE_URL.to_string(),
+        }
+    }
+
+    pub fn with_base_url(client_id: &str, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        AuthEndpoints {
+            client_id: client_id.to_string(),
+            device_code_url: format!("{}/devicecode", base_url),
+            token_url: format!("{}/token", base_url),
+            xbox_live_url: format!("{}/user/authenticate", base_url),
+            xsts_url: format!("{}/xsts/authorize", base_url),
+            minecraft_login_url: format!("{}/authentication/login_with_xbox", base_url),
+            minecraft_profile_url: format!("{}/minecraft/profile", base_url),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuthTokens {
+    access_token: String,
+    refresh_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuthError {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxResponse {
+    #[serde(rename = "Token")]
+    token: String,
+    #[serde(rename = "DisplayClaims")]
+    display_claims: XboxDisplayClaims,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxDisplayClaims {
+    xui: Vec<XboxUserInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxUserInfo {
+    uhs: String,
+    #[serde(default)]
+    xid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct XstsError {
+    #[serde(rename = "XErr")]
+    xerr: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MinecraftLogin {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MinecraftProfile {
+    id: String,
+    name: String,
+}
+
+pub struct MicrosoftAuth {
+    endpoints: AuthEndpoints,
+    client: reqwest::blocking::Client,
+}
+
+impl MicrosoftAuth {
+    pub fn new(endpoints: AuthEndpoints) -> Self {
+        MicrosoftAuth {
+            endpoints,
+            client: DownloadEngine::shared().client().clone(),
+        }
+    }
+
+    pub fn request_device_code(&self) -> Result<DeviceCode, Box<dyn Error>> {
+        if self.endpoints.client_id.is_empty() {
+            return Err("No Microsoft client id configured (set MSA_CLIENT_ID)".into());
+        }
+
+        let response = self
+            .client
+            .post(&self.endpoints.device_code_url)
+            .form(&[
+                ("client_id", self.endpoints.client_id.as_str()),
+                ("scope", SCOPE),
+            ])
+            .send()?;
+        if !response.status().is_success() {
+            return Err(format!("Device code request failed: {}", response.status()).into());
+        }
+
+        Ok(response.json()?)
+    }
+
+    pub fn poll_device_code(
+        &self,
+        device_code: &DeviceCode,
+    ) -> Result<MicrosoftAccount, Box<dyn Error>> {
+        let deadline = now_secs() + device_code.expires_in;
+        let mut interval = device_code.interval.max(1);
+
+        loop {
+            if now_secs() >= deadline {
+                return Err("The login code expired. Please try again.".into());
+            }
+
+            thread::sleep(Duration::from_secs(interval));
+
+            let response = self
+                .client
+                .post(&self.endpoints.token_url)
+                .form(&[
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                    ("client_id", self.endpoints.client_id.as_str()),
+                    ("device_code", device_code.device_code.as_str()),
+                ])
+                .send()?;
+
+            if response.status().is_success() {
+                let tokens: OAuthTokens = response.json()?;
+                return self.complete_login(tokens);
+            }
+
+            let error: OAuthError = response.json()?;
+            match error.error.as_str() {
+                "authorization_pending" => {}
+                "slow_down" => interval += 5,
+                _ => {
+                    return Err(format!(
+                        "Microsoft login failed: {}",
+                        error.error_description.unwrap_or(error.error)
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+
+    pub fn refresh(&self, account: &MicrosoftAccount) -> Result<MicrosoftAccount, Box<dyn Error>> {
+        let response = self
+            .client
+            .post(&self.endpoints.token_url)
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", self.endpoints.client_id.as_str()),
+                ("refresh_token", account.refresh_token.as_str()),
+                ("scope", SCOPE),
+            ])
+            .send()?;
+
+        if !response.status().is_success() {
+            let error: OAuthError = response.json()?;
+            return Err(format!(
+                "Failed to refresh Microsoft login: {}",
+                error.error_description.unwrap_or(error.error)
+            )
+            .into());
+        }
+
+        let tokens: OAuthTokens = response.json()?;
+        self.complete_login(tokens)
+    }
+
+    pub fn ensure_valid(
+        &self,
+        account: &MicrosoftAccount,
+    ) -> Result<MicrosoftAccount, Box<dyn Error>> {
+        if account.expires_at > now_secs() + REFRESH_MARGIN_SECS {
+            Ok(account.clone())
+        } else {
+            self.refresh(account)
+        }
+    }
+
+    fn complete_login(&self, tokens: OAuthTokens) -> Result<MicrosoftAccount, Box<dyn Error>> {
+        let xbox_live: XboxResponse = self
+            .client
+            .post(&self.endpoints.xbox_live_url)
+            .json(&json!({
+                "Properties": {
+                    "AuthMethod": "RPS",
+                    "SiteName": "user.auth.xboxlive.com",
+                    "RpsTicket": format!("d={}", tokens.access_token),
+                },
+                "RelyingParty": "http://auth.xboxlive.com",
+                "TokenType": "JWT",
+            }))
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        let response = self
+            .client
+            .post(&self.endpoints.xsts_url)
+            .json(&json!({
+                "Properties": {
+                    "SandboxId": "RETAIL",
+                    "UserTokens": [xbox_live.token],
+                },
+                "RelyingParty": "rp://api.minecraftservices.com/",
+                "TokenType": "JWT",
+            }))
+            .send()?;
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let error: XstsError = response.json()?;
+            return Err(xsts_error_message(error.xerr).into());
+        }
+        let xsts: XboxResponse = response.error_for_status()?.json()?;
+
+        let user_info = xsts
+            .display_claims
+            .xui
+            .first()
+            .ok_or("Xbox Live did not return a user hash")?;
+
+        let login: MinecraftLogin = self
+            .client
+            .post(&self.endpoints.minecraft_login_url)
+            .json(&json!({
+                "identityToken": format!("XBL3.0 x={};{}", user_info.uhs, xsts.token),
+            }))
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        let response = self
+            .client
+            .get(&self.endpoints.minecraft_profile_url)
+            .bearer_auth(&login.access_token)
+            .send()?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err("This Microsoft account does not own Minecraft".into());
+        }
+        let profile: MinecraftProfile = response.error_for_status()?.json()?;
+
+        Ok(MicrosoftAccount {
+            uuid: hyphenate_uuid(&profile.id),
+            name: profile.name,
+            access_token: login.access_token,
+            expires_at: now_secs() + login.expires_in,
+            refresh_token: tokens.refresh_token,
+            xuid: user_info.xid.clone(),
+        })
+    }
+}
+
+fn xsts_error_message(xerr: u64) -> String {
+    match xerr {
+        2148916233 => "This Microsoft account has no Xbox profile. Sign in at minecraft.net first."
+            .to_string(),
+        2148916235 => "Xbox Live is not available in your country.".to_string(),
+        2148916236 | 2148916237 => "This account needs adult verification on xbox.com.".to_string(),
+        2148916238 => {
+            "This is a child account. An adult must add it to a Microsoft family.".to_string()
+        }
+        _ => format!("Xbox Live authorization failed (XErr {})", xerr),
+    }
+}
+
+fn hyphenate_uuid(id: &str) -> String {
+    if id.len() != 32 {
+        return id.to_string();
+    }
+    format!(
+        "{}-{}-{}-{}-{}",
+        &id[0..8],
+        &id[8..12],
+        &id[12..16],
+        &id[16..20],
+        &id[20..32]
+    )
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
\ No newline at end of file
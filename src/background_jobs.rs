@@ -0,0 +1,170 @@
+use crate::{
+    cancel::CancellationToken,
+    download_engine::{DownloadEngine, DownloadJob},
+    models::ByteProgress,
+};
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+#[derive(Clone, Debug)]
+pub struct JobFailure {
+    pub label: String,
+    pub error: String,
+    index: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct JobStatus {
+    pub completed: usize,
+    pub total: usize,
+    pub running: bool,
+    pub cancelled: bool,
+    pub bytes: ByteProgress,
+    pub failures: Vec<JobFailure>,
+}
+
+type SuccessHook = Box<dyn Fn(&DownloadJob) + Send + Sync>;
+
+pub struct BackgroundJob {
+    name: String,
+    engine: DownloadEngine,
+    jobs: Vec<DownloadJob>,
+    on_success: SuccessHook,
+    cancel: Mutex<CancellationToken>,
+    status: Mutex<JobStatus>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl BackgroundJob {
+    pub fn spawn(
+        name: &str,
+        engine: &DownloadEngine,
+        jobs: Vec<DownloadJob>,
+        cancel: &CancellationToken,
+        on_success: impl Fn(&DownloadJob) + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let job = Arc::new(BackgroundJob {
+            name: name.to_string(),
+            engine: engine.clone(),
+            status: Mutex::new(JobStatus {
+                total: jobs.len(),
+                ..Default::default()
+            }),
+            jobs,
+            on_success: Box::new(on_success),
+            cancel: Mutex::new(cancel.clone()),
+            worker: Mutex::new(None),
+        });
+
+        job.run((0..job.jobs.len()).collect());
+        job
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn status(&self) -> JobStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.lock().unwrap().cancel();
+    }
+
+    pub fn wait(&self) {
+        let worker = self.worker.lock().unwrap().take();
+        if let Some(worker) = worker {
+            let _ = worker.join();
+        }
+    }
+
+    pub fn retry(self: &Arc<Self>) -> bool {
+        let indices: Vec<usize> = {
+            let status = self.status.lock().unwrap();
+            if status.running || status.failures.is_empty() {
+                return false;
+            }
+            status
+                .failures
+                .iter()
+                .map(|failure| failure.index)
+                .collect()
+        };
+
+        *self.cancel.lock().unwrap() = CancellationToken::new();
+        self.run(indices);
+        true
+    }
+
+    fn run(self: &Arc<Self>, indices: Vec<usize>) {
+        {
+            let mut status = self.status.lock().unwrap();
+            status.running = true;
+            status.cancelled = false;
+            status.failures.clear();
+        }
+
+        let job = self.clone();
+        let cancel = self.cancel.lock().unwrap().clone();
+        let worker = thread::spawn(move || {
+            let jobs: Vec<DownloadJob> = indices.iter().map(|&i| job.jobs[i].clone()).collect();
+
+            let results = job.engine.run(&jobs, &cancel, |progress| {
+                if progress.finished && progress.error.is_none() {
+                    (job.on_success)(progress.job);
+                }
+
+                let mut status = job.status.lock().unwrap();
+                status.bytes = progress.bytes;
+                if progress.finished && progress.error.is_none() {
+                    status.completed += 1;
+                }
+            });
+
+            let mut status = job.status.lock().unwrap();
+            status.failures = indices
+                .iter()
+                .zip(&jobs)
+                .zip(results)
+                .filter_map(|((&index, download), result)| {
+                    result.err().map(|e| JobFailure {
+                        label: download.label.clone(),
+                        error: e.to_string(),
+                        index,
+                    })
+                })
+                .collect();
+            status.running = false;
+            status.cancelled = cancel.is_cancelled();
+        });
+
+        *self.worker.lock().unwrap() = Some(worker);
+    }
+}
+
+#[derive(Default)]
+pub struct BackgroundJobs {
+    jobs: Mutex<Vec<Arc<BackgroundJob>>>,
+}
+
+impl BackgroundJobs {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn add(&self, job: Arc<BackgroundJob>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|job| {
+            let status = job.status();
+            status.running || !status.failures.is_empty()
+        });
+        jobs.push(job);
+    }
+
+    pub fn list(&self) -> Vec<Arc<BackgroundJob>> {
+        self.jobs.lock().unwrap().clone()
+    }
+}
\ No newline at end of file
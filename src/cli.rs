@@ -0,0 +1,586 @@
+use crate::app_init::{get_game_directory, get_libraries_directory};
+use minecraft_launcher::{
+    Launcher,
+    game_log::LogLine,
+    integrity::IssueKind,
+    java_finder::{find_all_java_installations, find_compatible_java},
+    models::{LaunchProgress, LaunchSession, Profile, parse_uuid},
+    process_registry::{ProcessRegistry, STATS_FILE, format_duration},
+    profiles::{read_profiles, read_stats, refresh_account, write_profiles},
+    storage::{self, format_size},
+    version_manager::{ManifestService, get_version_list, local_version_path, resolve_version},
+};
+use serde_json::json;
+use std::{error::Error, path::PathBuf, sync::mpsc, thread};
+
+const USAGE: &str = "Usage: minecraft_launcher [--json] <command>
+
+Commands:
+  list-versions [--installed] [--type <type>]
+  install <version>...
+  launch <version> --profile <name> [--java <path>]
+  verify <version> [--repair]
+  profiles list
+  profiles add <name> [--jvm-args <args>] [--uuid <uuid>]
+  profiles edit <name> [--name <new name>] [--jvm-args <args>] [--uuid <uuid>]
+  profiles rm <name>
+  java list
+  storage usage
+  storage gc [--dry-run | --yes]
+  storage rm-version <version>";
+
+struct Output {
+    json: bool,
+}
+
+impl Output {
+    fn progress(&self, progress: &LaunchProgress) {
+        if self.json {
+            println!(
+                "{}",
+                json!({
+                    "stage": progress.stage,
+                    "current": progress.current,
+                    "total": progress.total,
+                    "percentage": progress.percentage(),
+                    "message": progress.message,
+                    "bytes": progress.bytes.map(|bytes| json!({
+                        "downloaded": bytes.downloaded,
+                        "total": bytes.total,
+                        "bytes_per_sec": bytes.bytes_per_sec,
+                        "eta_secs": bytes.eta().map(|eta| eta.as_secs()),
+                    })),
+                })
+            );
+        } else {
+            match &progress.bytes {
+                Some(bytes) if bytes.total > 0 => println!(
+                    "[{:>3.0}%] {} ({})",
+                    progress.percentage(),
+                    progress.message,
+                    bytes.format()
+                ),
+                _ => println!("[{:>3.0}%] {}", progress.percentage(), progress.message),
+            }
+        }
+    }
+
+    fn log(&self, line: &LogLine) {
+        if self.json {
+            println!(
+                "{}",
+                match &line.event {
+                    Some(event) => json!({
+                        "level": event.level.name(),
+                        "timestamp": event.timestamp,
+                        "thread": event.thread,
+                        "logger": event.logger,
+                        "message": event.message,
+                        "throwable": event.throwable,
+                    }),
+                    None => json!({ "level": line.level.name(), "line": line.text }),
+                }
+            );
+        } else {
+            println!("{}", line.text);
+        }
+    }
+
+    fn error(&self, message: &str) {
+        if self.json {
+            println!("{}", json!({ "error": message }));
+        } else {
+            eprintln!("error: {}", message);
+        }
+    }
+}
+
+pub fn run(args: &[String]) -> Option<i32> {
+    let mut args = args.to_vec();
+    let json = take_flag(&mut args, "--json");
+
+    if args.is_empty() {
+        return None;
+    }
+
+    let output = Output { json };
+    let result = match args[0].as_str() {
+        "list-versions" => list_versions(&mut args[1..].to_vec(), &output),
+        "install" => install(&args[1..], &output),
+        "launch" => launch(&mut args[1..].to_vec(), &output),
+        "verify" => verify(&mut args[1..].to_vec(), &output),
+        "profiles" => profiles(&mut args[1..].to_vec(), &output),
+        "java" => java(&args[1..], &output),
+        "storage" => storage(&mut args[1..].to_vec(), &output),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        command => Err(format!("Unknown command '{}'\n\n{}", command, USAGE).into()),
+    };
+
+    match result {
+        Ok(()) => Some(0),
+        Err(e) => {
+            output.error(&e.to_string());
+            Some(1)
+        }
+    }
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, Box<dyn Error>> {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) if index + 1 < args.len() => {
+            let value = args.remove(index + 1);
+            args.remove(index);
+            Ok(Some(value))
+        }
+        Some(_) => Err(format!("Missing value for {}", name).into()),
+        None => Ok(None),
+    }
+}
+
+fn positional<'a>(args: &'a [String], what: &str) -> Result<&'a str, Box<dyn Error>> {
+    match args {
+        [value] => Ok(value),
+        [] => Err(format!("Missing {}\n\n{}", what, USAGE).into()),
+        _ => Err(format!("Unexpected arguments: {}", args[1..].join(" ")).into()),
+    }
+}
+
+fn list_versions(args: &mut Vec<String>, output: &Output) -> Result<(), Box<dyn Error>> {
+    let installed_only = take_flag(args, "--installed");
+    let version_type = take_option(args, "--type")?;
+    positional_none(args)?;
+
+    let game_dir = get_game_directory();
+    let manifest = ManifestService::new(&game_dir);
+
+    for (id, kind) in get_version_list(&game_dir, &manifest) {
+        let installed = local_version_path(&game_dir, &id).exists();
+        if (installed_only && !installed) || version_type.as_ref().is_some_and(|t| *t != kind) {
+            continue;
+        }
+
+        if output.json {
+            println!(
+                "{}",
+                json!({ "id": id, "type": kind, "installed": installed })
+            );
+        } else {
+            println!(
+                "{}\t{}{}",
+                id,
+                kind,
+                if installed { "\tinstalled" } else { "" }
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn install(args: &[String], output: &Output) -> Result<(), Box<dyn Error>> {
+    if args.is_empty() {
+        return Err(format!("Missing version id\n\n{}", USAGE).into());
+    }
+
+    let game_dir = get_game_directory();
+    let manifest = ManifestService::new(&game_dir);
+    for version_id in args {
+        let version_data = resolve_version(&game_dir, &manifest, version_id)?;
+        let game_dir = game_dir.clone();
+        with_progress(output, move |sender| {
+            Ok(launcher(game_dir, sender).install(&version_data)?)
+        })?;
+    }
+
+    Ok(())
+}
+
+fn verify(args: &mut Vec<String>, output: &Output) -> Result<(), Box<dyn Error>> {
+    let repair = take_flag(args, "--repair");
+    let version_id = positional(args, "version id")?;
+
+    let game_dir = get_game_directory();
+    let manifest = ManifestService::new(&game_dir);
+    let version_data = resolve_version(&game_dir, &manifest, version_id)?;
+
+    let (report, repaired) = with_progress(output, move |sender| {
+        let launcher = launcher(game_dir, sender);
+        let report = launcher.verify(&version_data)?;
+        let repaired = if repair && !report.is_ok() {
+            Some(launcher.repair(&version_data, &report)?)
+        } else {
+            None
+        };
+        Ok((report, repaired))
+    })?;
+
+    for issue in &report.issues {
+        if output.json {
+            println!(
+                "{}",
+                json!({ "issue": issue.kind.name(), "path": issue.path })
+            );
+        } else {
+            println!("{}\t{}", issue.kind.name(), issue.path.display());
+        }
+    }
+
+    let report = repaired.unwrap_or(report);
+    if output.json {
+        println!(
+            "{}",
+            json!({
+                "checked": report.checked,
+                "missing": report.count(IssueKind::Missing),
+                "corrupt": report.count(IssueKind::Corrupt),
+                "extra": report.count(IssueKind::Extra),
+            })
+        );
+    } else {
+        println!("{}", report.summary());
+    }
+
+    match (report.is_ok(), repair) {
+        (true, _) => Ok(()),
+        (false, true) => Err("Some files could not be repaired".into()),
+        (false, false) => Err(format!(
+            "{} is damaged, run 'verify {} --repair' to fix it",
+            version_id, version_id
+        )
+        .into()),
+    }
+}
+
+fn launch(args: &mut Vec<String>, output: &Output) -> Result<(), Box<dyn Error>> {
+    let profile_name = take_option(args, "--profile")?.ok_or("Missing --profile <name>")?;
+    let java_path = take_option(args, "--java")?.map(PathBuf::from);
+    let version_id = positional(args, "version id")?.to_string();
+
+    let game_dir = get_game_directory();
+    let profiles_path = game_dir.join("profiles.json");
+    let mut profiles = read_profiles(&profiles_path)?;
+    let profile = profiles
+        .iter_mut()
+        .find(|p| p.username == profile_name)
+        .ok_or_else(|| format!("Profile '{}' not found", profile_name))?;
+
+    let session = match &profile.account {
+        Some(account) => {
+            let (refreshed, session) = refresh_account(account)?;
+            if refreshed != *account {
+                profile.account = Some(refreshed);
+                write_profiles(&profiles_path, &profiles)?;
+            }
+            session
+        }
+        None => LaunchSession::offline(profile),
+    };
+
+    let jvm_args = profiles
+        .iter()
+        .find(|p| p.username == profile_name)
+        .and_then(|p| p.jvm_args.clone())
+        .map(|args| args.split_whitespace().map(str::to_string).collect());
+
+    let manifest = ManifestService::new(&game_dir);
+    let version_data = resolve_version(&game_dir, &manifest, &version_id)?;
+    let java_path = java_path.or_else(|| {
+        version_data
+            .get_required_java_version()
+            .and_then(|required| find_compatible_java(required, false))
+    });
+
+    let registry = ProcessRegistry::new(&game_dir);
+    let (log_sender, log_receiver) = mpsc::channel();
+    let process = with_progress(output, move |sender| {
+        let launcher = Launcher::builder(game_dir)
+            .cache_dir(get_libraries_directory())
+            .progress(sender)
+            .log(log_sender)
+            .build();
+        launcher.install(&version_data)?;
+        Ok(launcher.launch(&version_data, &session, java_path, jvm_args)?)
+    })?;
+    registry.register(&profile_name, process);
+
+    for line in log_receiver {
+        output.log(&line);
+    }
+    registry.wait_all();
+
+    Ok(())
+}
+
+fn launcher(game_dir: PathBuf, sender: mpsc::Sender<LaunchProgress>) -> Launcher {
+    Launcher::builder(game_dir)
+        .cache_dir(get_libraries_directory())
+        .progress(sender)
+        .build()
+}
+
+fn with_progress<T: Send + 'static>(
+    output: &Output,
+    task: impl FnOnce(mpsc::Sender<LaunchProgress>) -> Result<T, Box<dyn Error>> + Send + 'static,
+) -> Result<T, Box<dyn Error>> {
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::spawn(move || task(sender).map_err(|e| e.to_string()));
+
+    for progress in receiver {
+        output.progress(&progress);
+    }
+
+    worker
+        .join()
+        .map_err(|_| "Worker thread panicked")?
+        .map_err(|e| e.into())
+}
+
+fn profiles(args: &mut Vec<String>, output: &Output) -> Result<(), Box<dyn Error>> {
+    let game_dir = get_game_directory();
+    let profiles_path = game_dir.join("profiles.json");
+    let mut profiles = read_profiles(&profiles_path)?;
+
+    if args.is_empty() {
+        return Err(format!("Missing profiles subcommand\n\n{}", USAGE).into());
+    }
+    let subcommand = args.remove(0);
+
+    match subcommand.as_str() {
+        "list" => {
+            positional_none(args)?;
+            let stats = read_stats(&game_dir.join(STATS_FILE))?;
+            for profile in &profiles {
+                let play_time = stats
+                    .get(&profile.username)
+                    .map_or(0, |stats| stats.play_time_secs);
+                if output.json {
+                    println!(
+                        "{}",
+                        json!({
+                            "name": profile.username,
+                            "uuid": profile.offline_uuid(),
+                            "jvm_args": profile.jvm_args,
+                            "account": profile.account.is_some(),
+                            "play_time_secs": play_time,
+                        })
+                    );
+                } else {
+                    println!(
+                        "{}\t{}\t{}",
+                        profile.username,
+                        profile.offline_uuid(),
+                        format_duration(play_time)
+                    );
+                }
+            }
+            return Ok(());
+        }
+        "add" => {
+            let jvm_args = take_option(args, "--jvm-args")?;
+            let uuid = take_option(args, "--uuid")?
+                .map(|uuid| parse_uuid(&uuid))
+                .transpose()?
+                .flatten();
+            let name = positional(args, "profile name")?.to_string();
+            if profiles.iter().any(|p| p.username == name) {
+                return Err(format!("Profile '{}' already exists", name).into());
+            }
+
+            profiles.push(Profile {
+                username: name,
+                jvm_args,
+                uuid,
+                account: None,
+            });
+        }
+        "edit" => {
+            let new_name = take_option(args, "--name")?;
+            let jvm_args = take_option(args, "--jvm-args")?;
+            let uuid = take_option(args, "--uuid")?;
+            let name = positional(args, "profile name")?;
+            let profile = profiles
+                .iter_mut()
+                .find(|p| p.username == name)
+                .ok_or_else(|| format!("Profile '{}' not found", name))?;
+
+            if let Some(new_name) = new_name {
+                profile.username = new_name;
+            }
+            if let Some(jvm_args) = jvm_args {
+                profile.jvm_args = Some(jvm_args).filter(|args| !args.is_empty());
+            }
+            if let Some(uuid) = uuid {
+                profile.uuid = parse_uuid(&uuid)?;
+            }
+        }
+        "rm" => {
+            let name = positional(args, "profile name")?;
+            let count = profiles.len();
+            profiles.retain(|p| p.username != name);
+            if profiles.len() == count {
+                return Err(format!("Profile '{}' not found", name).into());
+            }
+        }
+        subcommand => {
+            return Err(format!("Unknown profiles subcommand '{}'", subcommand).into());
+        }
+    }
+
+    write_profiles(&profiles_path, &profiles)
+}
+
+fn positional_none(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Unexpected arguments: {}", args.join(" ")).into())
+    }
+}
+
+fn java(args: &[String], output: &Output) -> Result<(), Box<dyn Error>> {
+    match args {
+        [subcommand] if subcommand == "list" => {
+            for (path, version) in find_all_java_installations() {
+                if output.json {
+                    println!(
+                        "{}",
+                        json!({ "version": version, "path": path.to_string_lossy() })
+                    );
+                } else {
+                    println!("{}\t{}", version, path.display());
+                }
+            }
+            Ok(())
+        }
+        _ => Err(format!("Unknown java subcommand\n\n{}", USAGE).into()),
+    }
+}
+
+fn storage(args: &mut Vec<String>, output: &Output) -> Result<(), Box<dyn Error>> {
+    let game_dir = get_game_directory();
+    let libraries_dir = get_libraries_directory();
+
+    if args.is_empty() {
+        return Err(format!("Missing storage subcommand\n\n{}", USAGE).into());
+    }
+    let subcommand = args.remove(0);
+
+    match subcommand.as_str() {
+        "usage" => {
+            positional_none(args)?;
+            let usage = storage::disk_usage(&game_dir, &libraries_dir);
+            if output.json {
+                println!(
+                    "{}",
+                    json!({
+                        "total": usage.total(),
+                        "categories": usage.categories.iter().map(|(category, bytes)| {
+                            json!({ "category": category.name(), "bytes": bytes })
+                        }).collect::<Vec<_>>(),
+                        "versions": usage.versions.iter().map(|(id, bytes)| {
+                            json!({ "id": id, "bytes": bytes })
+                        }).collect::<Vec<_>>(),
+                    })
+                );
+            } else {
+                for (category, bytes) in &usage.categories {
+                    println!("{}\t{}", category.name(), format_size(*bytes));
+                }
+                println!("Total\t{}", format_size(usage.total()));
+                println!();
+                for (id, bytes) in &usage.versions {
+                    println!("{}\t{}", id, format_size(*bytes));
+                }
+            }
+            Ok(())
+        }
+        "gc" => {
+            let dry_run = take_flag(args, "--dry-run");
+            let yes = take_flag(args, "--yes");
+            positional_none(args)?;
+
+            let plan = storage::find_unused(&game_dir, &libraries_dir);
+            for warning in &plan.warnings {
+                output.error(warning);
+            }
+            for file in &plan.files {
+                if output.json {
+                    println!(
+                        "{}",
+                        json!({
+                            "category": file.category.name(),
+                            "path": file.path,
+                            "bytes": file.bytes,
+                        })
+                    );
+                } else {
+                    println!(
+                        "{}\t{}\t{}",
+                        file.category.name(),
+                        format_size(file.bytes),
+                        file.path.display()
+                    );
+                }
+            }
+
+            if dry_run {
+                if !output.json {
+                    println!(
+                        "{} unused files, {} would be freed",
+                        plan.files.len(),
+                        format_size(plan.bytes())
+                    );
+                }
+                return Ok(());
+            }
+            if !yes {
+                return Err(
+                    "Close the game and any other launcher, then run 'storage gc --yes' \
+                     to delete these files"
+                        .into(),
+                );
+            }
+
+            let result = storage::collect_garbage(&game_dir, &libraries_dir, &plan);
+            for (path, error) in &result.failed {
+                output.error(&format!("Failed to remove {}: {}", path.display(), error));
+            }
+            if output.json {
+                println!(
+                    "{}",
+                    json!({
+                        "removed": result.removed,
+                        "bytes": result.bytes,
+                        "skipped": result.skipped,
+                        "failed": result.failed.len(),
+                    })
+                );
+            } else {
+                println!(
+                    "Removed {} files, freed {}",
+                    result.removed,
+                    format_size(result.bytes)
+                );
+            }
+            Ok(())
+        }
+        "rm-version" => {
+            let version_id = positional(args, "version id")?;
+            storage::remove_version(&game_dir, version_id)
+        }
+        subcommand => Err(format!("Unknown storage subcommand '{}'", subcommand).into()),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,356 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+#[derive(Debug)]
+pub struct KnownIssue {
+    pub title: &'static str,
+    pub hint: &'static str,
+    patterns: &'static [&'static str],
+}
+
+const MOD_CONFLICT: KnownIssue = KnownIssue {
+    title: "Mod conflict",
+    hint: "Remove or update the suspected mods and make sure every mod matches the installed loader and Minecraft version.",
+    patterns: &[
+        "DuplicateModsFoundException",
+        "Found duplicate mods",
+        "ModResolutionException",
+        "Incompatible mods found",
+        "Incompatible mod set",
+        "Missing or unsupported mandatory dependencies",
+        "Mixin apply failed",
+        "MixinApplyError",
+    ],
+};
+
+const KNOWN_ISSUES: &[KnownIssue] = &[
+    KnownIssue {
+        title: "Out of memory",
+        hint: "Increase the memory allocation (for example -Xmx4G) in the profile JVM arguments or close other programs.",
+        patterns: &[
+            "java.lang.OutOfMemoryError",
+            "Java heap space",
+            "insufficient memory for the Java Runtime Environment",
+            "Could not reserve enough space",
+            "JVM allocation heap",
+        ],
+    },
+    KnownIssue {
+        title: "Wrong Java version",
+        hint: "This version needs a different Java release. Choose the Java version it requires in the Java list.",
+        patterns: &[
+            "UnsupportedClassVersionError",
+            "Unsupported major.minor version",
+            "compiled by a more recent version of the Java Runtime",
+            "cannot be cast to class java.net.URLClassLoader",
+            "Unrecognized VM option",
+        ],
+    },
+    KnownIssue {
+        title: "Missing OpenGL support",
+        hint: "Update your video card drivers or make sure your computer supports the required OpenGL version.",
+        patterns: &[
+            "No OpenGL context",
+            "no valid OpenGL",
+            "OpenGL Error",
+            "Pixel format not accelerated",
+            "GLFW error 65542",
+            "GLFW error 65543",
+            "does not appear to support OpenGL",
+            "atio6axx.dll",
+            "ig9icd64.dll",
+            "ig75icd64.dll",
+            "nvoglv64.dll",
+        ],
+    },
+    MOD_CONFLICT,
+    KnownIssue {
+        title: "Missing game files",
+        hint: "Game files or mods are missing or corrupted. Install the version again.",
+        patterns: &[
+            "java.lang.ClassNotFoundException",
+            "java.lang.NoClassDefFoundError",
+            "java.lang.UnsatisfiedLinkError",
+        ],
+    },
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct CrashAnalysis {
+    pub report_path: Option<PathBuf>,
+    pub description: Option<String>,
+    pub exception: Option<String>,
+    pub suspected_mods: Vec<String>,
+    pub issues: Vec<&'static KnownIssue>,
+}
+
+pub fn analyze(game_dir: &Path, since: SystemTime, log_tail: &[String]) -> CrashAnalysis {
+    let report_path = newest_report(game_dir, since);
+    let report = report_path
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+
+    let mut analysis = if report.contains("A fatal error has been detected by the Java Runtime") {
+        parse_hs_err(&report)
+    } else {
+        parse_crash_report(&report)
+    };
+    analysis.report_path = report_path;
+
+    let text = format!("{}\n{}", report, log_tail.join("\n"));
+    analysis.issues = KNOWN_ISSUES
+        .iter()
+        .filter(|issue| issue.patterns.iter().any(|pattern| text.contains(pattern)))
+        .collect();
+
+    if !analysis.suspected_mods.is_empty()
+        && !analysis
+            .issues
+            .iter()
+            .any(|issue| issue.title == MOD_CONFLICT.title)
+    {
+        analysis.issues.push(&MOD_CONFLICT);
+    }
+
+    analysis
+}
+
+fn newest_report(game_dir: &Path, since: SystemTime) -> Option<PathBuf> {
+    let crash_reports = fs::read_dir(game_dir.join("crash-reports"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"));
+
+    let hs_err_logs = fs::read_dir(game_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("hs_err_pid") && name.ends_with(".log"))
+        });
+
+    crash_reports
+        .chain(hs_err_logs)
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            (modified >= since).then_some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+fn parse_crash_report(report: &str) -> CrashAnalysis {
+    let mut analysis = CrashAnalysis::default();
+    let mut lines = report.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(description) = line.strip_prefix("Description:") {
+            analysis.description = Some(description.trim().to_string());
+
+            analysis.exception = lines
+                .by_ref()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string);
+        } else if line.trim_start().starts_with("Suspected Mod") {
+            let value = line.split_once(':').map(|(_, value)| value.trim());
+            match value {
+                Some(value) if !value.is_empty() => {
+                    if !value.eq_ignore_ascii_case("none") && !value.starts_with("~~") {
+                        analysis.suspected_mods.push(value.to_string());
+                    }
+                }
+                _ => {
+                    let indent = indentation(line);
+                    while let Some(next) = lines.next_if(|next| indentation(next) > indent) {
+                        let next = next.trim();
+                        if !next.is_empty() {
+                            analysis.suspected_mods.push(next.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    analysis
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn parse_hs_err(report: &str) -> CrashAnalysis {
+    let comments: Vec<&str> = report
+        .lines()
+        .take_while(|line| line.starts_with('#'))
+        .map(|line| line.trim_start_matches('#').trim())
+        .collect();
+
+    let description = comments
+        .iter()
+        .find(|line| line.starts_with("There is insufficient memory"))
+        .or_else(|| {
+            comments
+                .iter()
+                .skip_while(|line| !line.starts_with("A fatal error has been detected"))
+                .skip(1)
+                .find(|line| !line.is_empty())
+        })
+        .map(|line| line.to_string());
+
+    let exception = comments
+        .iter()
+        .skip_while(|line| !line.starts_with("Problematic frame"))
+        .nth(1)
+        .map(|frame| format!("Problematic frame: {}", frame));
+
+    CrashAnalysis {
+        description,
+        exception,
+        ..Default::default()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "minecraft_launcher_crash_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn titles(analysis: &CrashAnalysis) -> Vec<&'static str> {
+        analysis.issues.iter().map(|issue| issue.title).collect()
+    }
+
+    #[test]
+    fn detects_issues_in_log_tail() {
+        let game_dir = test_dir("log_tail");
+        let log_tail = vec![
+            "[12:00:00] [main/INFO]: Loading".to_string(),
+            "Exception in thread \"main\" java.lang.OutOfMemoryError: Java heap space".to_string(),
+        ];
+
+        let analysis = analyze(&game_dir, SystemTime::UNIX_EPOCH, &log_tail);
+
+        assert_eq!(titles(&analysis), ["Out of memory"]);
+        assert!(analysis.report_path.is_none());
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn parses_crash_report_with_suspected_mods() {
+        let game_dir = test_dir("report");
+        let report_path = game_dir
+            .join("crash-reports")
+            .join("crash-2024-01-01_12.00.00-client.txt");
+        fs::create_dir_all(report_path.parent().unwrap()).unwrap();
+        fs::write(
+            &report_path,
+            "---- Minecraft Crash Report ----\n\
+             Description: Rendering overlay\n\
+             \n\
+             java.lang.NullPointerException: Cannot invoke \"Foo.bar()\"\n\
+             \tat net.minecraft.Foo.render(Foo.java:10)\n\
+             \n\
+             \tSuspected Mods: \n\
+             \t\tSodium (sodium)\n\
+             \t\tIris (iris)\n\
+             \tStacktrace:\n",
+        )
+        .unwrap();
+
+        let analysis = analyze(&game_dir, SystemTime::UNIX_EPOCH, &[]);
+
+        assert_eq!(analysis.report_path.as_ref(), Some(&report_path));
+        assert_eq!(analysis.description.as_deref(), Some("Rendering overlay"));
+        assert_eq!(
+            analysis.exception.as_deref(),
+            Some("java.lang.NullPointerException: Cannot invoke \"Foo.bar()\"")
+        );
+        assert_eq!(analysis.suspected_mods, ["Sodium (sodium)", "Iris (iris)"]);
+        assert_eq!(titles(&analysis), ["Mod conflict"]);
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn inline_suspected_mod_none_is_ignored() {
+        let analysis = parse_crash_report(
+            "Description: Ticking entity\n\njava.lang.Error\n\tSuspected Mod: NONE\n",
+        );
+
+        assert!(analysis.suspected_mods.is_empty());
+        assert_eq!(analysis.exception.as_deref(), Some("java.lang.Error"));
+    }
+
+    #[test]
+    fn parses_hs_err_log() {
+        let game_dir = test_dir("hs_err");
+        fs::write(
+            game_dir.join("hs_err_pid1234.log"),
+            "#\n\
+             # A fatal error has been detected by the Java Runtime Environment:\n\
+             #\n\
+             #  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x0, pid=1234, tid=5678\n\
+             #\n\
+             # Problematic frame:\n\
+             # C  [atio6axx.dll+0x1234]\n\
+             #\n\
+             ---------------  S U M M A R Y ------------\n",
+        )
+        .unwrap();
+
+        let analysis = analyze(&game_dir, SystemTime::UNIX_EPOCH, &[]);
+
+        assert_eq!(
+            analysis.description.as_deref(),
+            Some("EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x0, pid=1234, tid=5678")
+        );
+        assert_eq!(
+            analysis.exception.as_deref(),
+            Some("Problematic frame: C  [atio6axx.dll+0x1234]")
+        );
+        assert_eq!(titles(&analysis), ["Missing OpenGL support"]);
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn ignores_reports_older_than_launch() {
+        let game_dir = test_dir("old_report");
+        let crash_reports = game_dir.join("crash-reports");
+        fs::create_dir_all(&crash_reports).unwrap();
+        fs::write(
+            crash_reports.join("crash-old.txt"),
+            "Description: Old crash\n",
+        )
+        .unwrap();
+
+        let since = SystemTime::now() + Duration::from_secs(60);
+        let analysis = analyze(&game_dir, since, &[]);
+
+        assert!(analysis.report_path.is_none());
+        assert!(analysis.description.is_none());
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+}
\ No newline at end of file
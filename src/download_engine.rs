@@ -0,0 +1,417 @@
+use crate::{
+    cancel::CancellationToken,
+    downloader::{is_file_valid, to_hex},
+    error::DownloadError,
+    models::ByteProgress,
+};
+use reqwest::blocking::Client;
+use sha1::{Digest, Sha1};
+use std::{
+    cell::Cell,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_CONCURRENCY: usize = 8;
+const QUEUE_SIZE_PER_WORKER: usize = 4;
+const MAX_ATTEMPTS: u32 = 4;
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+const BYTE_REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+static SHARED_ENGINE: LazyLock<DownloadEngine> = LazyLock::new(DownloadEngine::default);
+
+#[derive(Clone, Debug)]
+pub struct DownloadJob {
+    pub url: String,
+    pub dest: PathBuf,
+    pub sha1: Option<String>,
+    pub size: Option<u64>,
+    pub label: String,
+}
+
+impl DownloadJob {
+    pub fn new(url: &str, dest: &Path, sha1: Option<&str>, size: Option<u64>) -> Self {
+        DownloadJob {
+            url: url.to_string(),
+            dest: dest.to_path_buf(),
+            sha1: sha1.map(str::to_string),
+            size,
+            label: dest
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| url.to_string()),
+        }
+    }
+
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+}
+
+pub type ProgressCallback<'a> = &'a (dyn Fn(&JobProgress) + Sync);
+
+pub struct JobProgress<'a> {
+    pub job: &'a DownloadJob,
+    pub completed: usize,
+    pub total: usize,
+    pub finished: bool,
+    pub error: Option<&'a DownloadError>,
+    pub bytes: ByteProgress,
+}
+
+struct Transfer {
+    total: u64,
+    downloaded: AtomicU64,
+    transferred: AtomicU64,
+    started: Instant,
+    last_report: Mutex<Instant>,
+}
+
+impl Transfer {
+    fn new(jobs: &[DownloadJob]) -> Self {
+        let now = Instant::now();
+        Transfer {
+            total: jobs.iter().filter_map(|job| job.size).sum(),
+            downloaded: AtomicU64::new(0),
+            transferred: AtomicU64::new(0),
+            started: now,
+            last_report: Mutex::new(now),
+        }
+    }
+
+    fn add(&self, bytes: u64) {
+        self.downloaded.fetch_add(bytes, Ordering::SeqCst);
+    }
+
+    fn remove(&self, bytes: u64) {
+        self.downloaded.fetch_sub(bytes, Ordering::SeqCst);
+    }
+
+    fn add_transferred(&self, bytes: u64) {
+        self.transferred.fetch_add(bytes, Ordering::SeqCst);
+    }
+
+    fn should_report(&self) -> bool {
+        let mut last_report = self.last_report.lock().unwrap();
+        if last_report.elapsed() < BYTE_REPORT_INTERVAL {
+            return false;
+        }
+        *last_report = Instant::now();
+        true
+    }
+
+    fn snapshot(&self) -> ByteProgress {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let transferred = self.transferred.load(Ordering::SeqCst) as f64;
+        ByteProgress {
+            downloaded: self.downloaded.load(Ordering::SeqCst),
+            total: self.total,
+            bytes_per_sec: if elapsed > 0.0 {
+                transferred / elapsed
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DownloadEngine {
+    client: Client,
+    concurrency: usize,
+}
+
+impl Default for DownloadEngine {
+    fn default() -> Self {
+        let client = Client::builder()
+            .pool_max_idle_per_host(DEFAULT_CONCURRENCY * 2)
+            .tcp_keepalive(Some(Duration::from_secs(30)))
+            .build()
+            .unwrap_or_default();
+
+        DownloadEngine::new(client)
+    }
+}
+
+impl DownloadEngine {
+    pub fn new(client: Client) -> Self {
+        DownloadEngine {
+            client,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    pub fn shared() -> &'static DownloadEngine {
+        &SHARED_ENGINE
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn download(
+        &self,
+        job: &DownloadJob,
+        cancel: &CancellationToken,
+    ) -> Result<(), DownloadError> {
+        self.fetch(job, cancel, &|_, _| {})
+    }
+
+    fn fetch(
+        &self,
+        job: &DownloadJob,
+        cancel: &CancellationToken,
+        on_bytes: &dyn Fn(u64, u64),
+    ) -> Result<(), DownloadError> {
+        let sha1 = job.sha1.as_deref();
+        if job.dest.exists() && is_file_valid(&job.dest, sha1, job.size) {
+            return Ok(());
+        }
+
+        if let Some(parent) = job.dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let part_path = part_path(&job.dest);
+        let mut attempt = 0;
+        loop {
+            match self.download_part(&job.url, &part_path, sha1, job.size, cancel, on_bytes) {
+                Ok(()) => {
+                    fs::rename(&part_path, &job.dest)?;
+                    return Ok(());
+                }
+                Err(e) if attempt + 1 < MAX_ATTEMPTS && is_transient(&e) => {
+                    if is_corrupt_part(&e) {
+                        let _ = fs::remove_file(&part_path);
+                    }
+                    cancel.sleep(RETRY_DELAY * 2u32.pow(attempt))?;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    if !is_transient(&e) && !matches!(e, DownloadError::Cancelled) {
+                        let _ = fs::remove_file(&part_path);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    pub fn run(
+        &self,
+        jobs: &[DownloadJob],
+        cancel: &CancellationToken,
+        on_progress: impl Fn(&JobProgress) + Sync,
+    ) -> Vec<Result<(), DownloadError>> {
+        let total = jobs.len();
+        let workers = self.concurrency.min(total);
+        let (sender, receiver) = mpsc::sync_channel::<usize>(workers * QUEUE_SIZE_PER_WORKER);
+        let receiver = Mutex::new(receiver);
+        let results: Mutex<Vec<Option<Result<(), DownloadError>>>> =
+            Mutex::new((0..total).map(|_| None).collect());
+        let completed = AtomicUsize::new(0);
+        let transfer = Transfer::new(jobs);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    loop {
+                        let next = receiver.lock().unwrap().recv();
+                        let Ok(index) = next else {
+                            break;
+                        };
+
+                        let job = &jobs[index];
+                        let reported = Cell::new(0);
+                        let on_bytes = |written: u64, received: u64| {
+                            if written >= reported.get() {
+                                transfer.add(written - reported.get());
+                            } else {
+                                transfer.remove(reported.get() - written);
+                            }
+                            transfer.add_transferred(received);
+                            reported.set(written);
+
+                            if transfer.should_report() {
+                                on_progress(&JobProgress {
+                                    job,
+                                    completed: completed.load(Ordering::SeqCst),
+                                    total,
+                                    finished: false,
+                                    error: None,
+                                    bytes: transfer.snapshot(),
+                                });
+                            }
+                        };
+
+                        let result = self.fetch(job, cancel, &on_bytes);
+                        match (&result, job.size) {
+                            (Ok(()), Some(size)) if size > reported.get() => {
+                                transfer.add(size - reported.get());
+                            }
+                            (Err(_), _) => transfer.remove(reported.get()),
+                            _ => {}
+                        }
+
+                        on_progress(&JobProgress {
+                            job,
+                            completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+                            total,
+                            finished: true,
+                            error: result.as_ref().err(),
+                            bytes: transfer.snapshot(),
+                        });
+                        results.lock().unwrap()[index] = Some(result);
+                    }
+                });
+            }
+
+            for index in 0..total {
+                if cancel.is_cancelled() || sender.send(index).is_err() {
+                    break;
+                }
+            }
+            drop(sender);
+        });
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.unwrap_or(Err(DownloadError::Cancelled)))
+            .collect()
+    }
+
+    fn download_part(
+        &self,
+        url: &str,
+        part_path: &Path,
+        sha1: Option<&str>,
+        size: Option<u64>,
+        cancel: &CancellationToken,
+        on_bytes: &dyn Fn(u64, u64),
+    ) -> Result<(), DownloadError> {
+        cancel.check()?;
+
+        let mut hasher = Sha1::new();
+        let mut written = match fs::metadata(part_path) {
+            Ok(metadata) if size.is_none_or(|size| metadata.len() < size) => metadata.len(),
+            _ => 0,
+        };
+
+        let mut request = self.client.get(url);
+        if written > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", written));
+        }
+
+        let mut response = request.send()?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(DownloadError::HttpStatus {
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+
+        let mut part_file = if written > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT {
+            let mut existing = File::open(part_path)?;
+            io::copy(&mut existing, &mut HashWriter(&mut hasher))?;
+            fs::OpenOptions::new().append(true).open(part_path)?
+        } else {
+            written = 0;
+            File::create(part_path)?
+        };
+        on_bytes(written, 0);
+
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            cancel.check()?;
+            let read = response.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            part_file.write_all(&buffer[..read])?;
+            written += read as u64;
+            on_bytes(written, read as u64);
+        }
+        part_file.sync_all()?;
+
+        if let Some(expected_size) = size
+            && written != expected_size
+        {
+            return Err(DownloadError::SizeMismatch {
+                url: url.to_string(),
+                expected: expected_size,
+                actual: written,
+            });
+        }
+
+        if let Some(expected_sha1) = sha1 {
+            let actual_sha1 = to_hex(&hasher.finalize());
+            if !actual_sha1.eq_ignore_ascii_case(expected_sha1) {
+                return Err(DownloadError::ChecksumMismatch {
+                    url: url.to_string(),
+                    expected: expected_sha1.to_string(),
+                    actual: actual_sha1,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn part_path(dest_path: &Path) -> PathBuf {
+    let mut file_name = dest_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".part");
+    dest_path.with_file_name(file_name)
+}
+
+fn is_transient(error: &DownloadError) -> bool {
+    match error {
+        DownloadError::Http(_) | DownloadError::Io(_) => true,
+        DownloadError::HttpStatus { status, .. } => *status >= 500 || *status == 429,
+        _ => is_corrupt_part(error),
+    }
+}
+
+fn is_corrupt_part(error: &DownloadError) -> bool {
+    matches!(
+        error,
+        DownloadError::HttpStatus { status: 416, .. }
+            | DownloadError::SizeMismatch { .. }
+            | DownloadError::ChecksumMismatch { .. }
+    )
+}
+
+struct HashWriter<'a>(&'a mut Sha1);
+
+impl Write for HashWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
\ No newline at end of file
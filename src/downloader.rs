@@ -1,145 +1,285 @@
-use crate::models::{AssetIndexData, Extract, Library, VersionData};
-use rayon::{
-    ThreadPoolBuilder,
-    iter::{IntoParallelRefIterator, ParallelIterator},
+use crate::{
+    arguments::{current_os, rules_allow},
+    background_jobs::BackgroundJob,
+    cancel::CancellationToken,
+    download_engine::{DownloadEngine, DownloadJob, ProgressCallback},
+    error::DownloadError,
+    integrity::VerifyReport,
+    models::{AssetIndexData, Extract, Library, VersionData},
 };
+use sha1::{Digest, Sha1};
 use std::{
-    error::Error,
+    collections::HashSet,
     fs::{self, File},
-    io::{self, Write},
+    io::{self, Read},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use zip::ZipArchive;
 
-pub fn download_file(url: &str, dest_path: &Path) -> Result<(), Box<dyn Error>> {
-    if dest_path.exists() {
-        return Ok(());
+pub fn download_file(
+    url: &str,
+    dest_path: &Path,
+    sha1: Option<&str>,
+    size: Option<u64>,
+) -> Result<(), DownloadError> {
+    DownloadEngine::shared().download(
+        &DownloadJob::new(url, dest_path, sha1, size),
+        &CancellationToken::new(),
+    )
+}
+
+pub fn download_client_jar(
+    engine: &DownloadEngine,
+    game_dir: &Path,
+    version_data: &VersionData,
+    progress_callback: Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<PathBuf, DownloadError> {
+    let jar_path = client_jar_path(game_dir, version_data);
+
+    if let Some(job) = client_jar_job(game_dir, version_data) {
+        engine
+            .run(std::slice::from_ref(&job), cancel, |progress| {
+                if let Some(callback) = progress_callback {
+                    callback(progress);
+                }
+            })
+            .remove(0)?;
     }
 
-    let client = reqwest::blocking::Client::builder()
-        .pool_max_idle_per_host(10)
-        .tcp_keepalive(Some(std::time::Duration::from_secs(30)))
-        .build()?;
-
-    let response = client.get(url).send()?;
-    if !response.status().is_success() {
-        return Err(Box::new(io::Error::new(
-            io::ErrorKind::Other,
-            format!("Error downloading file: {}", response.status()),
-        )));
+    Ok(jar_path)
+}
+
+fn client_jar_path(game_dir: &Path, version_data: &VersionData) -> PathBuf {
+    game_dir
+        .join("versions")
+        .join(version_data.jar_id())
+        .join("client.jar")
+}
+
+pub(crate) fn client_jar_job(game_dir: &Path, version_data: &VersionData) -> Option<DownloadJob> {
+    let client = &version_data.downloads.as_ref()?.client;
+    Some(
+        DownloadJob::new(
+            &client.url,
+            &client_jar_path(game_dir, version_data),
+            client.sha1.as_deref(),
+            client.size,
+        )
+        .label(&format!("{}.jar", version_data.jar_id())),
+    )
+}
+
+pub fn download_logging_config(
+    engine: &DownloadEngine,
+    game_dir: &Path,
+    version_data: &VersionData,
+    cancel: &CancellationToken,
+) -> Result<Option<PathBuf>, DownloadError> {
+    let Some(job) = logging_config_job(game_dir, version_data) else {
+        return Ok(None);
+    };
+    engine.download(&job, cancel)?;
+
+    Ok(Some(job.dest))
+}
+
+pub(crate) fn logging_config_job(
+    game_dir: &Path,
+    version_data: &VersionData,
+) -> Option<DownloadJob> {
+    let config = version_data.logging.as_ref()?.client.as_ref()?;
+    Some(DownloadJob::new(
+        &config.file.url,
+        &game_dir
+            .join("assets")
+            .join("log_configs")
+            .join(&config.file.id),
+        config.file.sha1.as_deref(),
+        config.file.size,
+    ))
+}
+
+pub fn is_file_valid(path: &Path, sha1: Option<&str>, size: Option<u64>) -> bool {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ => return false,
+    };
+
+    if let Some(expected_size) = size
+        && metadata.len() != expected_size
+    {
+        return false;
     }
 
-    if let Some(parent) = dest_path.parent() {
-        fs::create_dir_all(parent)?;
+    match sha1 {
+        Some(expected_sha1) => match file_sha1(path) {
+            Ok(actual_sha1) => actual_sha1.eq_ignore_ascii_case(expected_sha1),
+            Err(_) => false,
+        },
+        None => true,
     }
+}
 
-    let mut dest_file = File::create(dest_path)?;
-    let bytes = response.bytes()?;
-    dest_file.write_all(&bytes)?;
+pub fn file_sha1(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha1::new();
+    let mut buffer = [0u8; 64 * 1024];
 
-    Ok(())
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(to_hex(&hasher.finalize()))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 pub fn download_libraries(
-    version_data: &VersionData,
+    engine: &DownloadEngine,
+    libraries: &[Library],
     libraries_dir: &Path,
-    mut progress_callback: Option<&mut dyn FnMut(usize, usize, &str)>,
-) -> Result<Vec<PathBuf>, Box<dyn Error>> {
-    let mut classpath = Vec::new();
+    progress_callback: Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<Vec<PathBuf>, DownloadError> {
     fs::create_dir_all(libraries_dir)?;
 
-    let libraries_to_download: Vec<&Library> = version_data
-        .libraries
-        .iter()
-        .filter(|lib| should_use_library(lib))
-        .collect();
+    let jobs = library_jobs(libraries, libraries_dir);
+
+    let results = engine.run(&jobs, cancel, |progress| {
+        if let Some(callback) = progress_callback {
+            callback(progress);
+        }
+    });
 
-    let total = libraries_to_download.len();
+    check_results(results)?;
 
-    for (i, library) in libraries_to_download.iter().enumerate() {
-        let name = library
-            .downloads
-            .as_ref()
-            .and_then(|d| d.artifact.as_ref())
-            .map(|a| a.path.clone())
-            .unwrap_or_else(|| "Unknown library".to_string());
+    Ok(jobs.into_iter().map(|job| job.dest).collect())
+}
 
-        if let Some(ref mut callback) = progress_callback {
-            callback(i, total, &name);
-        }
+fn check_results(results: Vec<Result<(), DownloadError>>) -> Result<(), DownloadError> {
+    let failures: Vec<DownloadError> = results.into_iter().filter_map(Result::err).collect();
 
-        if let Some(downloads) = &library.downloads {
-            if let Some(artifact) = &downloads.artifact {
-                let library_path = libraries_dir.join(&artifact.path);
-                if !library_path.exists() {
-                    if let Ok(_) = download_file(&artifact.url, &library_path) {
-                        classpath.push(library_path);
-                    }
-                } else {
-                    classpath.push(library_path);
-                }
-            }
-        }
+    if failures
+        .iter()
+        .any(|e| matches!(e, DownloadError::Cancelled))
+    {
+        return Err(DownloadError::Cancelled);
     }
 
-    Ok(classpath)
+    match failures.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn library_jobs(libraries: &[Library], libraries_dir: &Path) -> Vec<DownloadJob> {
+    libraries
+        .iter()
+        .filter(|lib| should_use_library(lib))
+        .filter_map(|lib| lib.artifact())
+        .map(|artifact| {
+            DownloadJob::new(
+                &artifact.url,
+                &libraries_dir.join(&artifact.path),
+                artifact.sha1.as_deref(),
+                artifact.size,
+            )
+            .label(&artifact.path)
+        })
+        .collect()
 }
 
 pub fn download_and_extract_natives(
+    engine: &DownloadEngine,
     version_data: &VersionData,
     natives_dir: &Path,
-) -> Result<(), Box<dyn Error>> {
+    cancel: &CancellationToken,
+) -> Result<(), DownloadError> {
+    let result = extract_natives(engine, version_data, natives_dir, cancel);
+    if let Err(DownloadError::Cancelled) = result {
+        let _ = fs::remove_dir_all(natives_dir);
+    }
+    result
+}
+
+fn extract_natives(
+    engine: &DownloadEngine,
+    version_data: &VersionData,
+    natives_dir: &Path,
+    cancel: &CancellationToken,
+) -> Result<(), DownloadError> {
     fs::create_dir_all(natives_dir)?;
 
-    for library in &version_data.libraries {
-        if !should_use_library(library) {
-            continue;
-        }
+    let (jobs, extracts): (Vec<DownloadJob>, Vec<&Option<Extract>>) =
+        native_jobs(version_data, natives_dir).into_iter().unzip();
 
-        if let Some(natives) = &library.natives {
-            let os = get_current_os();
-            if let Some(classifier) = natives.get(os) {
-                if let Some(downloads) = &library.downloads {
-                    if let Some(classifiers) = &downloads.classifiers {
-                        if let Some(artifact) = classifiers.get(classifier) {
-                            let natives_jar_path = natives_dir.join(&artifact.path);
-                            if let Some(parent) = natives_jar_path.parent() {
-                                fs::create_dir_all(parent)?;
-                            }
-                            if !natives_jar_path.exists() {
-                                download_file(&artifact.url, &natives_jar_path)?;
-                            }
-                            extract_natives_from_jar(
-                                &natives_jar_path,
-                                natives_dir,
-                                &library.extract,
-                            )?;
-                        }
-                    }
-                }
-            }
-        }
+    let results = engine.run(&jobs, cancel, |_| {});
+    for ((job, extract), result) in jobs.iter().zip(extracts).zip(results) {
+        result?;
+        extract_natives_from_jar(&job.dest, natives_dir, extract, cancel)?;
     }
     Ok(())
 }
 
-fn extract_natives_from_jar(
+pub(crate) fn native_jobs<'a>(
+    version_data: &'a VersionData,
+    natives_dir: &Path,
+) -> Vec<(DownloadJob, &'a Option<Extract>)> {
+    let os = current_os();
+    version_data
+        .libraries
+        .iter()
+        .filter(|library| should_use_library(library))
+        .filter_map(|library| {
+            let classifier = library.natives.as_ref()?.get(os)?;
+            let artifact = library
+                .downloads
+                .as_ref()?
+                .classifiers
+                .as_ref()?
+                .get(classifier)?;
+            let job = DownloadJob::new(
+                &artifact.url,
+                &natives_dir.join(&artifact.path),
+                artifact.sha1.as_deref(),
+                artifact.size,
+            )
+            .label(&artifact.path);
+            Some((job, &library.extract))
+        })
+        .collect()
+}
+
+pub(crate) fn is_native_excluded(file_name: &str, extract: &Option<Extract>) -> bool {
+    extract
+        .as_ref()
+        .and_then(|extract| extract.exclude.as_ref())
+        .is_some_and(|exclude| exclude.iter().any(|e| file_name.starts_with(e)))
+}
+
+pub(crate) fn extract_natives_from_jar(
     jar_path: &Path,
     dest_dir: &Path,
     extract: &Option<Extract>,
-) -> Result<(), Box<dyn Error>> {
+    cancel: &CancellationToken,
+) -> Result<(), DownloadError> {
     let file = File::open(jar_path)?;
     let mut archive = ZipArchive::new(file)?;
     for i in 0..archive.len() {
+        cancel.check()?;
         let mut file = archive.by_index(i)?;
         let file_name = file.name().to_string();
 
-        if let Some(extract_info) = extract {
-            if let Some(exclude) = &extract_info.exclude {
-                if exclude.iter().any(|e| file_name.starts_with(e)) {
-                    continue;
-                }
-            }
+        if is_native_excluded(&file_name, extract) {
+            continue;
         }
 
         let outpath = dest_dir.join(file_name);
@@ -157,239 +297,180 @@ fn extract_natives_from_jar(
 }
 
 pub fn download_and_extract_assets(
+    engine: &DownloadEngine,
     version_data: &VersionData,
     game_dir: &Path,
-    progress_callback: Option<impl Fn(usize, usize, &str) + Send + Sync>,
-) -> Result<(), Box<dyn Error>> {
-    let _ = ThreadPoolBuilder::new().num_threads(16).build_global();
-
-    let asset_index_url = &version_data.asset_index.url;
-    let asset_index_id = &version_data.asset_index.id;
+    progress_callback: Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<Option<Arc<BackgroundJob>>, DownloadError> {
+    let asset_index = version_data.asset_index.as_ref().ok_or_else(|| {
+        DownloadError::InvalidMetadata("Version data does not declare an asset index".to_string())
+    })?;
+    let asset_index_id = &asset_index.id;
     let assets_dir = game_dir.join("assets");
     let indexes_dir = assets_dir.join("indexes");
     let objects_dir = assets_dir.join("objects");
-    let legacy_dir = assets_dir.join("legacy");
-    let resource_dir = assets_dir.join("resources");
 
     fs::create_dir_all(&indexes_dir)?;
     fs::create_dir_all(&objects_dir)?;
 
-    let asset_index_path = indexes_dir.join(format!("{}.json", asset_index_id));
-    if !asset_index_path.exists() {
-        download_file(asset_index_url, &asset_index_path)?;
-    }
+    let index_job = asset_index_job(game_dir, version_data)?;
+    engine.download(&index_job, cancel)?;
 
-    let asset_index_content = fs::read_to_string(&asset_index_path)?;
+    let asset_index_content = fs::read_to_string(&index_job.dest)?;
     let asset_index_data: AssetIndexData = serde_json::from_str(&asset_index_content)?;
 
-    let is_legacy = asset_index_id == "legacy" || asset_index_id == "pre-1.6";
-
-    if is_legacy {
-        fs::create_dir_all(&legacy_dir)?;
+    let virtual_dir = if asset_index_id == "legacy" || asset_index_id == "pre-1.6" {
+        Some(assets_dir.join("legacy"))
     } else if asset_index_id == "1.7.10" || asset_index_id.parse::<f32>().unwrap_or(0.0) <= 1.8 {
-        fs::create_dir_all(&resource_dir)?;
+        Some(assets_dir.join("resources"))
+    } else {
+        None
+    };
+    if let Some(virtual_dir) = &virtual_dir {
+        fs::create_dir_all(virtual_dir)?;
     }
 
-    let assets_to_download: Vec<(String, PathBuf, String)> = asset_index_data
-        .objects
-        .iter()
-        .map(|(virtual_path, asset)| {
-            let hash = &asset.hash;
-            let first_two = &hash[0..2];
-            let asset_url = format!(
-                "https://resources.download.minecraft.net/{}/{}",
-                first_two, hash
-            );
-            let asset_dest = objects_dir.join(first_two).join(hash);
-            (asset_url, asset_dest, virtual_path.clone())
-        })
-        .collect();
+    let assets_to_download = asset_object_jobs(&asset_index_data, &objects_dir);
 
-    let _total_assets = assets_to_download.len();
-    let counter = std::sync::atomic::AtomicUsize::new(0);
-
-    let required_assets: Vec<_> = assets_to_download
-        .iter()
-        .filter(|(_, _, path)| {
+    let (required_assets, remaining_assets): (Vec<DownloadJob>, Vec<DownloadJob>) =
+        assets_to_download.into_iter().partition(|asset| {
+            let path = &asset.label;
             path.contains("minecraft/sounds/ui/")
                 || path.contains("minecraft/sounds/random/click")
                 || path.contains("minecraft/lang/")
                 || path.contains("minecraft/textures/gui/")
                 || path.contains("minecraft/font/")
-        })
-        .collect();
-
-    if !required_assets.is_empty() {
-        let required_total = required_assets.len();
-
-        required_assets
-            .par_iter()
-            .for_each(|(url, dest, virtual_path)| {
-                let current = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
-
-                if let Some(callback) = &progress_callback {
-                    callback(
-                        current.min(required_total),
-                        required_total,
-                        &format!("Required: {}", virtual_path),
-                    );
-                }
+        });
 
-                if let Some(parent) = dest.parent() {
-                    if let Err(_) = fs::create_dir_all(parent) {
-                        return;
-                    }
-                }
+    let results = engine.run(&required_assets, cancel, |progress| {
+        if let Some(callback) = progress_callback {
+            callback(progress);
+        }
+        if progress.finished && progress.error.is_none() {
+            copy_virtual_asset(progress.job, virtual_dir.as_deref());
+        }
+    });
 
-                if !dest.exists() {
-                    if let Err(_) = download_file(url, dest) {
-                        return;
-                    }
-                }
+    cancel.check()?;
+    check_results(results)?;
 
-                if is_legacy {
-                    let legacy_file_path = legacy_dir.join(virtual_path);
-
-                    if let Some(parent) = legacy_file_path.parent() {
-                        if let Err(_) = fs::create_dir_all(parent) {
-                            return;
-                        }
-                    }
-
-                    if !legacy_file_path.exists() {
-                        let _ = fs::copy(dest, &legacy_file_path);
-                    }
-                } else if asset_index_id == "1.7.10"
-                    || asset_index_id.parse::<f32>().unwrap_or(0.0) <= 1.8
-                {
-                    let resource_file_path = resource_dir.join(virtual_path);
-
-                    if let Some(parent) = resource_file_path.parent() {
-                        if let Err(_) = fs::create_dir_all(parent) {
-                            return;
-                        }
-                    }
-
-                    if !resource_file_path.exists() {
-                        let _ = fs::copy(dest, &resource_file_path);
-                    }
-                }
-            });
+    if remaining_assets.is_empty() {
+        return Ok(None);
     }
 
-    if let Some(callback) = &progress_callback {
-        callback(100, 100, "Required assets downloaded. Launching game...");
-    }
+    Ok(Some(BackgroundJob::spawn(
+        &format!("Assets {}", asset_index_id),
+        engine,
+        remaining_assets,
+        cancel,
+        move |asset| copy_virtual_asset(asset, virtual_dir.as_deref()),
+    )))
+}
 
-    let remaining_assets: Vec<(String, PathBuf, String)> = assets_to_download
-        .into_iter()
-        .filter(|(_, dest, _)| !dest.exists())
-        .collect();
+pub(crate) fn asset_index_job(
+    game_dir: &Path,
+    version_data: &VersionData,
+) -> Result<DownloadJob, DownloadError> {
+    let asset_index = version_data.asset_index.as_ref().ok_or_else(|| {
+        DownloadError::InvalidMetadata("Version data does not declare an asset index".to_string())
+    })?;
+    Ok(DownloadJob::new(
+        &asset_index.url,
+        &game_dir
+            .join("assets")
+            .join("indexes")
+            .join(format!("{}.json", asset_index.id)),
+        asset_index.sha1.as_deref(),
+        asset_index.size,
+    ))
+}
 
-    if !remaining_assets.is_empty() {
-        let _objects_dir_clone = objects_dir.to_path_buf();
-        let legacy_dir_clone = legacy_dir.to_path_buf();
-        let resource_dir_clone = resource_dir.to_path_buf();
-        let asset_index_id_clone = asset_index_id.to_string();
-        let is_legacy_clone = is_legacy;
+pub(crate) fn asset_object_jobs(
+    asset_index_data: &AssetIndexData,
+    objects_dir: &Path,
+) -> Vec<DownloadJob> {
+    asset_index_data
+        .objects
+        .iter()
+        .map(|(virtual_path, asset)| {
+            let hash = &asset.hash;
+            let first_two = &hash[0..2];
+            let asset_url = format!(
+                "https://resources.download.minecraft.net/{}/{}",
+                first_two, hash
+            );
+            DownloadJob::new(
+                &asset_url,
+                &objects_dir.join(first_two).join(hash),
+                Some(hash.as_str()),
+                asset.size,
+            )
+            .label(virtual_path)
+        })
+        .collect()
+}
 
-        std::thread::spawn(move || {
-            if remaining_assets.is_empty() {
-                return;
-            }
+fn copy_virtual_asset(asset: &DownloadJob, virtual_dir: Option<&Path>) {
+    let Some(virtual_dir) = virtual_dir else {
+        return;
+    };
 
-            let _remaining_total = remaining_assets.len();
-            let remaining_counter = std::sync::atomic::AtomicUsize::new(0);
-
-            remaining_assets
-                .par_iter()
-                .for_each(|(url, dest, virtual_path)| {
-                    let _current =
-                        remaining_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
-
-                    if let Some(parent) = dest.parent() {
-                        if let Err(_) = fs::create_dir_all(parent) {
-                            return;
-                        }
-                    }
-
-                    if !dest.exists() {
-                        if let Err(_) = download_file(url, dest) {
-                            return;
-                        }
-                    }
-
-                    if is_legacy_clone {
-                        let legacy_file_path = legacy_dir_clone.join(virtual_path);
-
-                        if let Some(parent) = legacy_file_path.parent() {
-                            if let Err(_) = fs::create_dir_all(parent) {
-                                return;
-                            }
-                        }
-
-                        if !legacy_file_path.exists() {
-                            let _ = fs::copy(dest, &legacy_file_path);
-                        }
-                    } else if asset_index_id_clone == "1.7.10"
-                        || asset_index_id_clone.parse::<f32>().unwrap_or(0.0) <= 1.8
-                    {
-                        let resource_file_path = resource_dir_clone.join(virtual_path);
-
-                        if let Some(parent) = resource_file_path.parent() {
-                            if let Err(_) = fs::create_dir_all(parent) {
-                                return;
-                            }
-                        }
-
-                        if !resource_file_path.exists() {
-                            let _ = fs::copy(dest, &resource_file_path);
-                        }
-                    }
-                });
-        });
+    let virtual_path = virtual_dir.join(&asset.label);
+    if virtual_path.exists() {
+        return;
     }
-
-    Ok(())
+    if let Some(parent) = virtual_path.parent()
+        && fs::create_dir_all(parent).is_err()
+    {
+        return;
+    }
+    let _ = fs::copy(&asset.dest, &virtual_path);
 }
 
 fn should_use_library(library: &Library) -> bool {
-    if library.rules.is_empty() {
-        return true;
-    }
+    rules_allow(&library.rules, &[])
+}
 
-    let mut allow = false;
-
-    for rule in &library.rules {
-        let applies = match &rule.os {
-            Some(os) => match &os.name {
-                Some(name) => {
-                    #[cfg(target_os = "windows")]
-                    let current_os = "windows";
-                    #[cfg(target_os = "linux")]
-                    let current_os = "linux";
-                    #[cfg(target_os = "macos")]
-                    let current_os = "osx";
-                    name == current_os
-                }
-                None => true,
-            },
-            None => true,
-        };
+pub fn repair_files(
+    engine: &DownloadEngine,
+    report: &VerifyReport,
+    progress_callback: Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<(), DownloadError> {
+    let jobs: Vec<DownloadJob> = report
+        .broken()
+        .filter_map(|issue| issue.download.clone())
+        .collect();
+
+    let results = engine.run(&jobs, cancel, |progress| {
+        if let Some(callback) = progress_callback {
+            callback(progress);
+        }
+    });
+    cancel.check()?;
 
-        if applies {
-            allow = rule.action == "allow";
+    let failed: HashSet<&Path> = jobs
+        .iter()
+        .zip(&results)
+        .filter(|(_, result)| result.is_err())
+        .map(|(job, _)| job.dest.as_path())
+        .collect();
+
+    let mut extracted = HashSet::new();
+    for native in report.broken().filter_map(|issue| issue.extract.as_ref()) {
+        if failed.contains(native.jar.as_path()) || !extracted.insert(&native.jar) {
+            continue;
         }
+        extract_natives_from_jar(&native.jar, &native.natives_dir, &native.extract, cancel)?;
     }
 
-    allow
-}
+    if !failed.is_empty() {
+        return Err(DownloadError::Incomplete {
+            failed: failed.len(),
+            total: jobs.len(),
+        });
+    }
 
-fn get_current_os() -> &'static str {
-    #[cfg(target_os = "windows")]
-    return "windows";
-    #[cfg(target_os = "linux")]
-    return "linux";
-    #[cfg(target_os = "macos")]
-    return "osx";
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,170 @@
+use crate::crash_analyzer::CrashAnalysis;
+use std::{error::Error, fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum DownloadError {
+    Http(reqwest::Error),
+    HttpStatus {
+        url: String,
+        status: u16,
+    },
+    SizeMismatch {
+        url: String,
+        expected: u64,
+        actual: u64,
+    },
+    ChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+    InvalidMetadata(String),
+    Archive(zip::result::ZipError),
+    Incomplete {
+        failed: usize,
+        total: usize,
+    },
+    Cancelled,
+    Io(io::Error),
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DownloadError::Http(e) => write!(f, "Network error: {}", e),
+            DownloadError::HttpStatus { url, status } => {
+                write!(f, "Error downloading {}: HTTP {}", url, status)
+            }
+            DownloadError::SizeMismatch {
+                url,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Size mismatch for {}: expected {} bytes, got {}",
+                url, expected, actual
+            ),
+            DownloadError::ChecksumMismatch {
+                url,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "SHA-1 mismatch for {}: expected {}, got {}",
+                url, expected, actual
+            ),
+            DownloadError::InvalidMetadata(message) => write!(f, "Invalid metadata: {}", message),
+            DownloadError::Archive(e) => write!(f, "Failed to extract archive: {}", e),
+            DownloadError::Incomplete { failed, total } => {
+                write!(f, "{} of {} files failed to download", failed, total)
+            }
+            DownloadError::Cancelled => write!(f, "Download cancelled"),
+            DownloadError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for DownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DownloadError::Http(e) => Some(e),
+            DownloadError::Archive(e) => Some(e),
+            DownloadError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for DownloadError {
+    fn from(e: reqwest::Error) -> Self {
+        DownloadError::Http(e)
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    fn from(e: io::Error) -> Self {
+        DownloadError::Io(e)
+    }
+}
+
+impl From<zip::result::ZipError> for DownloadError {
+    fn from(e: zip::result::ZipError) -> Self {
+        DownloadError::Archive(e)
+    }
+}
+
+impl From<serde_json::Error> for DownloadError {
+    fn from(e: serde_json::Error) -> Self {
+        DownloadError::InvalidMetadata(e.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub enum LaunchError {
+    JavaNotFound(PathBuf),
+    PermissionDenied(PathBuf),
+    Spawn(io::Error),
+    ProcessStatus(io::Error),
+    ProcessExited {
+        code: i32,
+        log_tail: Vec<String>,
+        crash: Box<CrashAnalysis>,
+    },
+    AuthServer(String),
+    Cancelled,
+    Download(DownloadError),
+    Io(io::Error),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LaunchError::JavaNotFound(path) => write!(f, "Java not found: {}", path.display()),
+            LaunchError::PermissionDenied(path) => {
+                write!(f, "Permission denied while starting {}", path.display())
+            }
+            LaunchError::Spawn(e) => write!(f, "Failed to start game: {}", e),
+            LaunchError::ProcessStatus(e) => {
+                write!(f, "Failed to check game process status: {}", e)
+            }
+            LaunchError::ProcessExited { code: 0, .. } => {
+                write!(f, "Minecraft process ended unexpectedly but cleanly")
+            }
+            LaunchError::ProcessExited { code, crash, .. } => match crash.issues.first() {
+                Some(issue) => write!(f, "Game process exited with code {}: {}", code, issue.title),
+                None => write!(f, "Game process exited with code: {}", code),
+            },
+            LaunchError::AuthServer(message) => {
+                write!(f, "Failed to prepare authlib-injector: {}", message)
+            }
+            LaunchError::Cancelled => write!(f, "Launch cancelled"),
+            LaunchError::Download(e) => write!(f, "{}", e),
+            LaunchError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for LaunchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LaunchError::Spawn(e) | LaunchError::ProcessStatus(e) | LaunchError::Io(e) => Some(e),
+            LaunchError::Download(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<DownloadError> for LaunchError {
+    fn from(e: DownloadError) -> Self {
+        match e {
+            DownloadError::Cancelled => LaunchError::Cancelled,
+            e => LaunchError::Download(e),
+        }
+    }
+}
+
+impl From<io::Error> for LaunchError {
+    fn from(e: io::Error) -> Self {
+        LaunchError::Io(e)
+    }
+}
\ No newline at end of file
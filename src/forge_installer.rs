@@ -0,0 +1,492 @@
+use crate::{
+    cancel::CancellationToken,
+    download_engine::DownloadEngine,
+    downloader::{self, download_file, file_sha1},
+    models::{Library, VersionData, maven_path},
+    version_manager::{ManifestService, local_version_path, resolve_version},
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use zip::ZipArchive;
+
+const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net";
+const NEOFORGE_MAVEN_URL: &str = "https://maven.neoforged.net/releases";
+
+static WORK_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForgeKind {
+    Forge,
+    NeoForge,
+}
+
+impl ForgeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForgeKind::Forge => "Forge",
+            ForgeKind::NeoForge => "NeoForge",
+        }
+    }
+
+    fn coordinates(&self) -> (&'static str, &'static str) {
+        match self {
+            ForgeKind::Forge => ("net.minecraftforge", "forge"),
+            ForgeKind::NeoForge => ("net.neoforged", "neoforge"),
+        }
+    }
+
+    fn artifact_path(&self) -> String {
+        let (group, artifact) = self.coordinates();
+        format!("{}/{}", group.replace('.', "/"), artifact)
+    }
+
+    fn default_maven_url(&self) -> &'static str {
+        match self {
+            ForgeKind::Forge => FORGE_MAVEN_URL,
+            ForgeKind::NeoForge => NEOFORGE_MAVEN_URL,
+        }
+    }
+
+    fn maven_url_env(&self) -> &'static str {
+        match self {
+            ForgeKind::Forge => "FORGE_MAVEN_URL",
+            ForgeKind::NeoForge => "NEOFORGE_MAVEN_URL",
+        }
+    }
+
+    fn version_prefix(&self, game_version: &str) -> String {
+        match self {
+            ForgeKind::Forge => format!("{}-", game_version),
+            ForgeKind::NeoForge => {
+                let mut parts = game_version.split('.').skip(1);
+                let major = parts.next().unwrap_or("0");
+                let minor = parts.next().unwrap_or("0");
+                format!("{}.{}.", major, minor)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallProfile {
+    #[serde(default)]
+    json: Option<String>,
+    #[serde(default)]
+    minecraft: Option<String>,
+    #[serde(default)]
+    data: HashMap<String, SidedData>,
+    #[serde(default)]
+    processors: Vec<Processor>,
+    #[serde(default)]
+    libraries: Vec<Library>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SidedData {
+    client: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Processor {
+    jar: String,
+    #[serde(default)]
+    classpath: Vec<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    outputs: HashMap<String, String>,
+    #[serde(default)]
+    sides: Option<Vec<String>>,
+}
+
+pub struct ForgeRepository {
+    pub kind: ForgeKind,
+    pub maven_url: String,
+}
+
+impl ForgeRepository {
+    pub fn new(kind: ForgeKind) -> Self {
+        let maven_url = std::env::var(kind.maven_url_env())
+            .unwrap_or_else(|_| kind.default_maven_url().to_string());
+        Self::with_base_url(kind, &maven_url)
+    }
+
+    pub fn with_base_url(kind: ForgeKind, maven_url: &str) -> Self {
+        ForgeRepository {
+            kind,
+            maven_url: maven_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn fetch_versions(&self, game_version: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let url = format!(
+            "{}/{}/maven-metadata.xml",
+            self.maven_url,
+            self.kind.artifact_path()
+        );
+        let response = DownloadEngine::shared().client().get(&url).send()?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "{} repository returned {}",
+                self.kind.name(),
+                response.status()
+            )
+            .into());
+        }
+
+        let metadata = response.text()?;
+        let prefix = self.kind.version_prefix(game_version);
+        let version_regex = regex::Regex::new(r"<version>([^<]+)</version>")?;
+
+        let mut versions: Vec<String> = version_regex
+            .captures_iter(&metadata)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .filter(|version| version.starts_with(&prefix))
+            .collect();
+        versions.reverse();
+
+        Ok(versions)
+    }
+
+    pub fn install(
+        &self,
+        game_dir: &Path,
+        libraries_dir: &Path,
+        java_path: &Path,
+        manifest: &ManifestService,
+        game_version: &str,
+        loader_version: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let (group, artifact) = self.kind.coordinates();
+        let installer_coordinates = format!("{}:{}:{}:installer", group, artifact, loader_version);
+        let installer_relative_path = maven_path(&installer_coordinates)
+            .ok_or_else(|| format!("Invalid installer coordinates {}", installer_coordinates))?;
+        let installer_path = libraries_dir.join(&installer_relative_path);
+        download_file(
+            &format!("{}/{}", self.maven_url, installer_relative_path),
+            &installer_path,
+            None,
+            None,
+        )?;
+
+        let mut installer = ZipArchive::new(File::open(&installer_path)?)?;
+
+        let install_profile: InstallProfile =
+            serde_json::from_str(&read_zip_entry(&mut installer, "install_profile.json")?)
+                .map_err(|e| {
+                    format!(
+                        "Unsupported {} installer (install_profile.json: {})",
+                        self.kind.name(),
+                        e
+                    )
+                })?;
+
+        let version_json_entry = install_profile
+            .json
+            .as_deref()
+            .unwrap_or("/version.json")
+            .trim_start_matches('/')
+            .to_string();
+        let version_json = read_zip_entry(&mut installer, &version_json_entry)?;
+        let version_data: VersionData = serde_json::from_str(&version_json)?;
+        if version_data.id.is_empty() {
+            return Err(format!("{} version.json has no id", self.kind.name()).into());
+        }
+
+        extract_maven_entries(&mut installer, libraries_dir)?;
+
+        let vanilla_version = install_profile
+            .minecraft
+            .clone()
+            .unwrap_or_else(|| game_version.to_string());
+        let vanilla = resolve_version(game_dir, manifest, &vanilla_version)?;
+        let engine = DownloadEngine::shared();
+        let cancel = CancellationToken::new();
+        let minecraft_jar =
+            downloader::download_client_jar(engine, game_dir, &vanilla, None, &cancel)?;
+
+        downloader::download_libraries(
+            engine,
+            &install_profile.libraries,
+            libraries_dir,
+            None,
+            &cancel,
+        )?;
+        downloader::download_libraries(
+            engine,
+            &version_data.libraries,
+            libraries_dir,
+            None,
+            &cancel,
+        )?;
+
+        let work_dir = std::env::temp_dir().join(format!(
+            "minecraft_launcher_{}_{}_{}",
+            version_data.id,
+            std::process::id(),
+            WORK_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&work_dir);
+        fs::create_dir_all(&work_dir)?;
+
+        let mut data = HashMap::new();
+        data.insert("SIDE".to_string(), "client".to_string());
+        data.insert(
+            "MINECRAFT_JAR".to_string(),
+            minecraft_jar.to_string_lossy().to_string(),
+        );
+        data.insert("MINECRAFT_VERSION".to_string(), vanilla_version.clone());
+        data.insert("ROOT".to_string(), game_dir.to_string_lossy().to_string());
+        data.insert(
+            "INSTALLER".to_string(),
+            installer_path.to_string_lossy().to_string(),
+        );
+        data.insert(
+            "LIBRARY_DIR".to_string(),
+            libraries_dir.to_string_lossy().to_string(),
+        );
+
+        let result = run_processors(
+            &install_profile,
+            &mut installer,
+            data,
+            &work_dir,
+            libraries_dir,
+            java_path,
+        );
+
+        let _ = fs::remove_dir_all(&work_dir);
+        result?;
+
+        let version_path = local_version_path(game_dir, &version_data.id);
+        if let Some(parent) = version_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&version_path, version_json)?;
+
+        Ok(version_data.id)
+    }
+}
+
+fn run_processors(
+    install_profile: &InstallProfile,
+    installer: &mut ZipArchive<File>,
+    mut data: HashMap<String, String>,
+    work_dir: &Path,
+    libraries_dir: &Path,
+    java_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    for (key, value) in &install_profile.data {
+        let resolved = resolve_data_value(&value.client, libraries_dir, installer, work_dir)?;
+        data.insert(key.clone(), resolved);
+    }
+
+    for processor in &install_profile.processors {
+        let runs_on_client = processor
+            .sides
+            .as_ref()
+            .is_none_or(|sides| sides.iter().any(|side| side == "client"));
+        if runs_on_client {
+            run_processor(processor, &data, libraries_dir, java_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_zip_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<String, Box<dyn Error>> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| format!("Installer does not contain {}", name))?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+fn extract_maven_entries(
+    archive: &mut ZipArchive<File>,
+    libraries_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let relative_path = match entry.enclosed_name() {
+            Some(path) => path,
+            None => continue,
+        };
+        let relative_path = match relative_path.strip_prefix("maven") {
+            Ok(path) => path.to_path_buf(),
+            Err(_) => continue,
+        };
+
+        let dest = libraries_dir.join(relative_path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut outfile = File::create(&dest)?;
+        io::copy(&mut entry, &mut outfile)?;
+    }
+    Ok(())
+}
+
+fn resolve_data_value(
+    value: &str,
+    libraries_dir: &Path,
+    archive: &mut ZipArchive<File>,
+    work_dir: &Path,
+) -> Result<String, Box<dyn Error>> {
+    if let Some(coordinates) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return Ok(library_path(libraries_dir, coordinates)?
+            .to_string_lossy()
+            .to_string());
+    }
+
+    if let Some(literal) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return Ok(literal.to_string());
+    }
+
+    if let Some(entry_name) = value.strip_prefix('/') {
+        let dest = work_dir.join(entry_name);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut entry = archive
+            .by_name(entry_name)
+            .map_err(|_| format!("Installer does not contain {}", entry_name))?;
+        let mut outfile = File::create(&dest)?;
+        io::copy(&mut entry, &mut outfile)?;
+        return Ok(dest.to_string_lossy().to_string());
+    }
+
+    Ok(value.to_string())
+}
+
+fn library_path(libraries_dir: &Path, coordinates: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let relative_path = maven_path(coordinates)
+        .ok_or_else(|| format!("Invalid library coordinates {}", coordinates))?;
+    Ok(libraries_dir.join(relative_path))
+}
+
+fn substitute_processor_arg(
+    arg: &str,
+    data: &HashMap<String, String>,
+    libraries_dir: &Path,
+) -> Result<String, Box<dyn Error>> {
+    if let Some(coordinates) = arg.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+        return Ok(library_path(libraries_dir, coordinates)?
+            .to_string_lossy()
+            .to_string());
+    }
+
+    if let Some(literal) = arg.strip_prefix('\'').and_then(|a| a.strip_suffix('\'')) {
+        return Ok(literal.to_string());
+    }
+
+    let mut result = arg.to_string();
+    for (key, value) in data {
+        result = result.replace(&format!("{{{}}}", key), value);
+    }
+    Ok(result)
+}
+
+fn jar_main_class(jar_path: &Path) -> Result<String, Box<dyn Error>> {
+    let mut archive = ZipArchive::new(File::open(jar_path)?)?;
+    let manifest = read_zip_entry(&mut archive, "META-INF/MANIFEST.MF")?;
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Main-Class:"))
+        .map(|main_class| main_class.trim().to_string())
+        .ok_or_else(|| format!("{} has no Main-Class", jar_path.display()).into())
+}
+
+fn run_processor(
+    processor: &Processor,
+    data: &HashMap<String, String>,
+    libraries_dir: &Path,
+    java_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let mut outputs = Vec::new();
+    for (path, sha1) in &processor.outputs {
+        outputs.push((
+            PathBuf::from(substitute_processor_arg(path, data, libraries_dir)?),
+            substitute_processor_arg(sha1, data, libraries_dir)?,
+        ));
+    }
+
+    let outputs_valid = !outputs.is_empty()
+        && outputs
+            .iter()
+            .all(|(path, sha1)| downloader::is_file_valid(path, Some(sha1), None));
+    if outputs_valid {
+        return Ok(());
+    }
+
+    let processor_jar = library_path(libraries_dir, &processor.jar)?;
+    let mut classpath = vec![processor_jar.clone()];
+    for coordinates in &processor.classpath {
+        classpath.push(library_path(libraries_dir, coordinates)?);
+    }
+    if let Some(missing) = classpath.iter().find(|path| !path.is_file()) {
+        return Err(format!("Processor library {} is missing", missing.display()).into());
+    }
+
+    let main_class = jar_main_class(&processor_jar)?;
+    let classpath = std::env::join_paths(&classpath)?;
+
+    let mut command = Command::new(java_path);
+    command.arg("-cp").arg(classpath).arg(&main_class);
+    for arg in &processor.args {
+        command.arg(substitute_processor_arg(arg, data, libraries_dir)?);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let tail: Vec<&str> = stderr.lines().rev().take(5).collect();
+        return Err(format!(
+            "Processor {} failed with {}: {}",
+            main_class,
+            output.status,
+            tail.into_iter().rev().collect::<Vec<_>>().join(" | ")
+        )
+        .into());
+    }
+
+    for (path, expected_sha1) in &outputs {
+        let actual_sha1 = file_sha1(path)?;
+        if !actual_sha1.eq_ignore_ascii_case(expected_sha1) {
+            let _ = fs::remove_file(path);
+            return Err(format!(
+                "Processor {} produced {} with SHA-1 {}, expected {}",
+                main_class,
+                path.display(),
+                actual_sha1,
+                expected_sha1
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
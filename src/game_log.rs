@@ -0,0 +1,525 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::Child,
+    sync::{Arc, Mutex, mpsc},
+    thread::{self, JoinHandle},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const LOGS_DIR: &str = "launcher_logs";
+const MAX_LOG_FILES: usize = 10;
+const LOG_TAIL_LINES: usize = 20;
+
+static OPEN_LOGS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn parse(line: &str) -> Option<LogLevel> {
+        let mut tags: Vec<&str> = line.split(']').collect();
+        tags.pop();
+
+        let level = tags
+            .into_iter()
+            .find_map(|tag| LogLevel::from_name(tag.rsplit(['/', '[']).next()?.trim()));
+
+        level.or_else(|| {
+            let line = line.trim_start();
+            (line.starts_with("Exception in thread") || line.starts_with("Caused by:"))
+                .then_some(LogLevel::Error)
+        })
+    }
+
+    pub fn from_name(name: &str) -> Option<LogLevel> {
+        match name {
+            "TRACE" | "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" | "WARNING" => Some(LogLevel::Warn),
+            "ERROR" | "FATAL" | "SEVERE" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub text: String,
+    pub event: Option<LogEvent>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEvent {
+    pub timestamp: Option<u64>,
+    pub thread: String,
+    pub level: LogLevel,
+    pub logger: String,
+    pub message: String,
+    pub throwable: Option<String>,
+}
+
+impl LogEvent {
+    pub fn parse(xml: &str) -> Option<LogEvent> {
+        let start = xml.find("<log4j:Event")?;
+        let open_tag = &xml[start..start + xml[start..].find('>')?];
+
+        Some(LogEvent {
+            timestamp: attribute(open_tag, "timestamp").and_then(|t| t.parse().ok()),
+            thread: attribute(open_tag, "thread").unwrap_or_default(),
+            level: attribute(open_tag, "level")
+                .and_then(|level| LogLevel::from_name(&level))
+                .unwrap_or(LogLevel::Info),
+            logger: attribute(open_tag, "logger").unwrap_or_default(),
+            message: element(xml, "log4j:Message").unwrap_or_default(),
+            throwable: element(xml, "log4j:Throwable").filter(|t| !t.trim().is_empty()),
+        })
+    }
+
+    pub fn format(&self) -> String {
+        let time = self
+            .timestamp
+            .map(|millis| {
+                let (_, _, _, hour, minute, second) = local_time(millis / 1000);
+                format!("{:02}:{:02}:{:02}", hour, minute, second)
+            })
+            .unwrap_or_default();
+
+        let mut text = format!(
+            "[{}] [{}/{}]: {}",
+            time,
+            self.thread,
+            self.level.name(),
+            self.message
+        );
+        if let Some(throwable) = &self.throwable {
+            text.push('\n');
+            text.push_str(throwable.trim_end());
+        }
+        text
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let key = format!(" {}=\"", name);
+    let start = tag.find(&key)? + key.len();
+    let end = start + tag[start..].find('"')?;
+    Some(unescape(&tag[start..end]))
+}
+
+fn element(xml: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    let content = &xml[start..end];
+
+    if content.contains("<![CDATA[") {
+        Some(
+            content
+                .split("<![CDATA[")
+                .flat_map(|part| part.split("]]>"))
+                .collect(),
+        )
+    } else {
+        Some(unescape(content))
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#13;", "")
+        .replace("&amp;", "&")
+}
+
+pub struct GameOutput {
+    path: PathBuf,
+    tail: Arc<Mutex<VecDeque<String>>>,
+    readers: Vec<JoinHandle<()>>,
+}
+
+impl GameOutput {
+    pub fn capture(
+        child: &mut Child,
+        logs_dir: &Path,
+        sink: Option<mpsc::Sender<LogLine>>,
+    ) -> io::Result<Self> {
+        let (path, file) = create_log_file(logs_dir, child.id())?;
+        let file = Arc::new(OpenLog::new(&path, file));
+        let tail = Arc::new(Mutex::new(VecDeque::new()));
+
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(spawn_reader(
+                stdout,
+                file.clone(),
+                tail.clone(),
+                sink.clone(),
+            ));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(spawn_reader(stderr, file, tail.clone(), sink));
+        }
+
+        Ok(GameOutput {
+            path,
+            tail,
+            readers,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn finish(self) -> Vec<String> {
+        for reader in self.readers {
+            let _ = reader.join();
+        }
+
+        self.tail.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+pub fn read_log_tail(path: &Path) -> Vec<String> {
+    let text = fs::read_to_string(path).unwrap_or_default();
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(LOG_TAIL_LINES)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+struct OpenLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl OpenLog {
+    fn new(path: &Path, file: File) -> Self {
+        OPEN_LOGS.lock().unwrap().push(path.to_path_buf());
+        OpenLog {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        }
+    }
+}
+
+impl Drop for OpenLog {
+    fn drop(&mut self) {
+        OPEN_LOGS.lock().unwrap().retain(|path| *path != self.path);
+    }
+}
+
+fn spawn_reader(
+    stream: impl Read + Send + 'static,
+    file: Arc<OpenLog>,
+    tail: Arc<Mutex<VecDeque<String>>>,
+    sink: Option<mpsc::Sender<LogLine>>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut level = LogLevel::Info;
+        let mut event_xml: Option<String> = None;
+
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            let log_line = match event_xml.as_mut() {
+                None if line.trim_start().starts_with("<log4j:Event") => {
+                    event_xml = Some(String::new());
+                    parse_event_line(&mut event_xml, &line)
+                }
+                Some(_) => parse_event_line(&mut event_xml, &line),
+                None => {
+                    level = LogLevel::parse(&line).unwrap_or(level);
+                    Some(LogLine {
+                        level,
+                        text: line,
+                        event: None,
+                    })
+                }
+            };
+
+            let Some(log_line) = log_line else {
+                continue;
+            };
+
+            let _ = writeln!(file.file.lock().unwrap(), "{}", log_line.text);
+
+            {
+                let mut tail = tail.lock().unwrap();
+                for text in log_line.text.lines() {
+                    if tail.len() == LOG_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(text.to_string());
+                }
+            }
+
+            if let Some(sink) = &sink {
+                let _ = sink.send(log_line);
+            }
+        }
+    })
+}
+
+fn parse_event_line(event_xml: &mut Option<String>, line: &str) -> Option<LogLine> {
+    let xml = event_xml.as_mut()?;
+    xml.push_str(line);
+    xml.push('\n');
+
+    if !line.contains("</log4j:Event>") {
+        return None;
+    }
+
+    let xml = event_xml.take()?;
+    Some(match LogEvent::parse(&xml) {
+        Some(event) => LogLine {
+            level: event.level,
+            text: event.format(),
+            event: Some(event),
+        },
+        None => LogLine {
+            level: LogLevel::Info,
+            text: xml.trim_end().to_string(),
+            event: None,
+        },
+    })
+}
+
+fn create_log_file(logs_dir: &Path, pid: u32) -> io::Result<(PathBuf, File)> {
+    fs::create_dir_all(logs_dir)?;
+    prune_logs(logs_dir, MAX_LOG_FILES - 1);
+
+    let name = format!("{}_{}", timestamp(), pid);
+    let mut attempt = 0;
+    loop {
+        let path = match attempt {
+            0 => logs_dir.join(format!("{}.log", name)),
+            _ => logs_dir.join(format!("{}-{}.log", name, attempt)),
+        };
+        match File::options().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn prune_logs(logs_dir: &Path, keep: usize) {
+    let open_logs = OPEN_LOGS.lock().unwrap().clone();
+    let mut logs: Vec<PathBuf> = match fs::read_dir(logs_dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+            .collect(),
+        Err(_) => return,
+    };
+
+    logs.sort();
+    if logs.len() > keep {
+        for path in &logs[..logs.len() - keep] {
+            if !open_logs.contains(path) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+fn timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let (year, month, day, hour, minute, second) = local_time(secs);
+    format!(
+        "{:04}-{:02}-{:02}_{:02}-{:02}-{:02}",
+        year, month, day, hour, minute, second
+    )
+}
+
+#[cfg(unix)]
+fn local_time(secs: u64) -> (i64, i64, i64, u64, u64, u64) {
+    let time = secs as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return civil_time(secs);
+    }
+
+    (
+        i64::from(tm.tm_year) + 1900,
+        i64::from(tm.tm_mon) + 1,
+        i64::from(tm.tm_mday),
+        tm.tm_hour as u64,
+        tm.tm_min as u64,
+        tm.tm_sec as u64,
+    )
+}
+
+#[cfg(windows)]
+fn local_time(secs: u64) -> (i64, i64, i64, u64, u64, u64) {
+    use winapi::um::{minwinbase::SYSTEMTIME, timezoneapi::SystemTimeToTzSpecificLocalTime};
+
+    let (year, month, day, hour, minute, second) = civil_time(secs);
+    let utc = SYSTEMTIME {
+        wYear: year as u16,
+        wMonth: month as u16,
+        wDayOfWeek: 0,
+        wDay: day as u16,
+        wHour: hour as u16,
+        wMinute: minute as u16,
+        wSecond: second as u16,
+        wMilliseconds: 0,
+    };
+    let mut local: SYSTEMTIME = unsafe { std::mem::zeroed() };
+    if unsafe { SystemTimeToTzSpecificLocalTime(std::ptr::null(), &utc, &mut local) } == 0 {
+        return (year, month, day, hour, minute, second);
+    }
+
+    (
+        i64::from(local.wYear),
+        i64::from(local.wMonth),
+        i64::from(local.wDay),
+        u64::from(local.wHour),
+        u64::from(local.wMinute),
+        u64::from(local.wSecond),
+    )
+}
+
+#[cfg(not(any(unix, windows)))]
+fn local_time(secs: u64) -> (i64, i64, i64, u64, u64, u64) {
+    civil_time(secs)
+}
+
+fn civil_time(secs: u64) -> (i64, i64, i64, u64, u64, u64) {
+    let days = (secs / 86400) as i64;
+    let (hour, minute, second) = (secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day, hour, minute, second)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT: &str = r#"<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1700000000000" level="ERROR" thread="Render thread">
+  <log4j:Message><![CDATA[Failed to load <texture>]]></log4j:Message>
+  <log4j:Throwable><![CDATA[java.lang.IllegalStateException: boom
+	at net.minecraft.Foo.bar(Foo.java:1)
+]]></log4j:Throwable>
+</log4j:Event>"#;
+
+    #[test]
+    fn parses_event_with_cdata() {
+        let event = LogEvent::parse(EVENT).unwrap();
+
+        assert_eq!(event.timestamp, Some(1700000000000));
+        assert_eq!(event.thread, "Render thread");
+        assert_eq!(event.level, LogLevel::Error);
+        assert_eq!(event.logger, "net.minecraft.client.Minecraft");
+        assert_eq!(event.message, "Failed to load <texture>");
+        assert!(
+            event
+                .throwable
+                .as_deref()
+                .is_some_and(|throwable| throwable.starts_with("java.lang.IllegalStateException"))
+        );
+    }
+
+    #[test]
+    fn parses_escaped_event_without_throwable() {
+        let event = LogEvent::parse(
+            r#"<log4j:Event logger="chat" timestamp="1" level="WARN" thread="Server &quot;main&quot;"><log4j:Message>a &lt; b &amp;&amp; c</log4j:Message><log4j:Throwable></log4j:Throwable></log4j:Event>"#,
+        )
+        .unwrap();
+
+        assert_eq!(event.level, LogLevel::Warn);
+        assert_eq!(event.thread, "Server \"main\"");
+        assert_eq!(event.message, "a < b && c");
+        assert!(event.throwable.is_none());
+    }
+
+    #[test]
+    fn unknown_level_defaults_to_info() {
+        let event = LogEvent::parse(
+            r#"<log4j:Event logger="x" thread="main"><log4j:Message>hi</log4j:Message></log4j:Event>"#,
+        )
+        .unwrap();
+
+        assert_eq!(event.level, LogLevel::Info);
+        assert_eq!(event.timestamp, None);
+        assert_eq!(event.format(), "[] [main/INFO]: hi");
+    }
+
+    #[test]
+    fn rejects_non_event_text() {
+        assert!(LogEvent::parse("[12:00:00] [main/INFO]: plain line").is_none());
+    }
+
+    #[test]
+    fn event_lines_are_collected_until_closing_tag() {
+        let mut event_xml = Some(String::new());
+        let lines: Vec<&str> = EVENT.lines().collect();
+
+        for line in &lines[..lines.len() - 1] {
+            assert!(parse_event_line(&mut event_xml, line).is_none());
+        }
+        let log_line = parse_event_line(&mut event_xml, lines[lines.len() - 1]).unwrap();
+
+        assert!(event_xml.is_none());
+        assert_eq!(log_line.level, LogLevel::Error);
+        assert!(
+            log_line
+                .text
+                .contains("[Render thread/ERROR]: Failed to load <texture>")
+        );
+        assert!(
+            log_line
+                .text
+                .contains("\n\tat net.minecraft.Foo.bar(Foo.java:1)")
+        );
+    }
+
+    #[test]
+    fn plain_lines_use_bracketed_level() {
+        assert_eq!(
+            LogLevel::parse("[12:00:00] [Render thread/WARN]: Missing sound"),
+            Some(LogLevel::Warn)
+        );
+        assert_eq!(
+            LogLevel::parse("Exception in thread \"main\" java.lang.Error"),
+            Some(LogLevel::Error)
+        );
+        assert_eq!(LogLevel::parse("no level here"), None);
+    }
+}
\ No newline at end of file
@@ -1,13 +1,15 @@
 use fltk::{
     app,
+    browser::HoldBrowser,
     button::{Button, CheckButton},
     draw,
     enums::{Align, Color, Event, Font, FrameType},
     frame::Frame,
     image::PngImage,
-    input::Input,
-    menu::Choice,
+    input::{Input, SecretInput},
+    menu::{Choice, MenuButton},
     prelude::*,
+    text::{TextBuffer, TextDisplay},
     window::Window,
 };
 
@@ -17,13 +19,26 @@ macro_rules! load_image_from_data {
     };
 }
 
-use crate::java_finder::find_all_java_installations;
-use crate::models::Profile;
+use minecraft_launcher::background_jobs::BackgroundJobs;
+use minecraft_launcher::crash_analyzer::CrashAnalysis;
+use minecraft_launcher::error::{DownloadError, LaunchError};
+use minecraft_launcher::game_log::{LogLevel, LogLine};
+use minecraft_launcher::install_queue::{InstallQueue, InstallState};
+use minecraft_launcher::integrity::VerifyReport;
+use minecraft_launcher::java_finder::find_all_java_installations;
+use minecraft_launcher::models::{LaunchProgress, MicrosoftAccount, Profile, parse_uuid};
+use minecraft_launcher::process_registry::{ProcessRegistry, format_duration};
+use minecraft_launcher::profiles::read_stats;
+use minecraft_launcher::storage::{DiskUsage, GcPlan, format_size};
+use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::process::Command;
+use std::rc::Rc;
 use std::sync::{
     Arc, Mutex,
     atomic::{AtomicBool, Ordering},
 };
+use std::time::SystemTime;
 
 #[cfg(target_os = "windows")]
 use crate::windows::adjust_window;
@@ -40,6 +55,32 @@ const BUTTON_X: i32 = 100;
 
 const DIALOG_WIDTH: i32 = 300;
 const DIALOG_HEIGHT: i32 = 200;
+const AUTH_DIALOG_HEIGHT: i32 = 280;
+const PROFILE_DIALOG_HEIGHT: i32 = 250;
+const CONSOLE_WIDTH: i32 = 600;
+const CONSOLE_HEIGHT: i32 = 400;
+const CONSOLE_SEARCH_WIDTH: i32 = 250;
+const CONSOLE_LEVEL_X: i32 = 360;
+const CONSOLE_LEVEL_WIDTH: i32 = 170;
+const CONSOLE_REFRESH_SECS: f64 = 0.25;
+const MAX_CONSOLE_LINES: usize = 5000;
+const CRASH_DIALOG_WIDTH: i32 = 480;
+const CRASH_DIALOG_HEIGHT: i32 = 320;
+const INSTANCES_WIDTH: i32 = 480;
+const INSTANCES_HEIGHT: i32 = 280;
+const INSTANCES_REFRESH_SECS: f64 = 1.0;
+const DOWNLOADS_WIDTH: i32 = 480;
+const DOWNLOADS_HEIGHT: i32 = 320;
+const DOWNLOADS_JOBS_HEIGHT: i32 = 70;
+const DOWNLOADS_REFRESH_SECS: f64 = 0.5;
+const MANAGER_WIDTH: i32 = 480;
+const MANAGER_HEIGHT: i32 = 300;
+const VERIFY_WIDTH: i32 = 520;
+const VERIFY_HEIGHT: i32 = 320;
+const VERIFY_KIND_WIDTH: i32 = 70;
+const STORAGE_WIDTH: i32 = 520;
+const STORAGE_HEIGHT: i32 = 400;
+const STORAGE_USAGE_HEIGHT: i32 = 120;
 
 const LABEL_HEIGHT: i32 = 20;
 const CONTROL_HEIGHT: i32 = 25;
@@ -49,6 +90,8 @@ const BUTTON_SPACING: i32 = 10;
 const INPUT_WIDTH: i32 = 260;
 const LABEL_WIDTH: i32 = 100;
 const FOLDER_BUTTON_WIDTH: i32 = 30;
+const TOOLS_BUTTON_WIDTH: i32 = 80;
+const CANCEL_BUTTON_WIDTH: i32 = 70;
 const JAVA_FIELD_WIDTH: i32 = 60;
 const JVM_ARGS_WIDTH: i32 = 160;
 const JVM_HINT_WIDTH: i32 = 135;
@@ -71,6 +114,13 @@ const JVM_HINT_X: i32 = 165;
 const JVM_INPUT_Y: i32 = 120;
 const JAVA_LABEL_Y: i32 = 130;
 const BUTTON_Y: i32 = 160;
+const PASSWORD_LABEL_Y: i32 = 150;
+const PASSWORD_INPUT_Y: i32 = 175;
+const AUTH_STATUS_Y: i32 = 205;
+const AUTH_BUTTON_Y: i32 = 240;
+const UUID_LABEL_Y: i32 = 150;
+const UUID_INPUT_Y: i32 = 175;
+const PROFILE_BUTTON_Y: i32 = 210;
 const BOTTOM_SECTION_Y: i32 = 165;
 const BUTTONS_Y_OFFSET: i32 = 25;
 const BUTTONS_MARGIN: i32 = 35;
@@ -93,6 +143,8 @@ const ERROR_TEXT_X: i32 = 90;
 const OK_BUTTON_X_WITH_DETAILS: i32 = 60;
 const OK_BUTTON_X_SOLO: i32 = 110;
 const DETAILS_BUTTON_X: i32 = 170;
+const RECOVERY_BUTTON_X: i32 = 15;
+const RECOVERY_BUTTON_WIDTH: i32 = 85;
 
 const PROGRESS_MARGIN: i32 = 15;
 const PROGRESS_BORDER: i32 = 2;
@@ -109,7 +161,7 @@ pub fn create_new_profile_dialog(text_font: Font) -> Option<Profile> {
     }
 
     let mut win = Window::default()
-        .with_size(DIALOG_WIDTH, DIALOG_HEIGHT)
+        .with_size(DIALOG_WIDTH, PROFILE_DIALOG_HEIGHT)
         .with_label("New Profile");
     win.set_border(false);
 
@@ -117,10 +169,10 @@ pub fn create_new_profile_dialog(text_font: Font) -> Option<Profile> {
     let screen_height = app::screen_size().1 as i32;
     win.set_pos(
         (screen_width - DIALOG_WIDTH) / CENTER_DIVISOR,
-        (screen_height - DIALOG_HEIGHT) / CENTER_DIVISOR,
+        (screen_height - PROFILE_DIALOG_HEIGHT) / CENTER_DIVISOR,
     );
 
-    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, DIALOG_HEIGHT, "");
+    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, PROFILE_DIALOG_HEIGHT, "");
     bg.set_frame(FrameType::FlatBox);
     bg.set_color(GRAY_COLOR);
 
@@ -166,7 +218,40 @@ pub fn create_new_profile_dialog(text_font: Font) -> Option<Profile> {
     jvm_args_input.set_text_font(text_font);
     jvm_args_input.set_text_size(FONT_SIZE);
 
-    let mut create_button = Button::new(BUTTON_X, BUTTON_Y, BUTTON_X, CONTROL_HEIGHT, "Create");
+    let mut uuid_label = Frame::new(
+        LEFT_MARGIN,
+        UUID_LABEL_Y,
+        JVM_ARGS_WIDTH,
+        CONTROL_HEIGHT,
+        "UUID (optional):",
+    );
+    uuid_label.set_label_font(text_font);
+    uuid_label.set_label_size(FONT_SIZE);
+    uuid_label.set_align(Align::Left | Align::Inside);
+
+    let mut uuid_hint_label = Frame::new(
+        JVM_HINT_X,
+        UUID_LABEL_Y,
+        JVM_HINT_WIDTH,
+        CONTROL_HEIGHT,
+        "Blank = offline UUID",
+    );
+    uuid_hint_label.set_label_font(text_font);
+    uuid_hint_label.set_label_size(SMALL_FONT_SIZE);
+    uuid_hint_label.set_label_color(HINT_TEXT_COLOR);
+    uuid_hint_label.set_align(Align::Left | Align::Inside);
+
+    let mut uuid_input = Input::new(LEFT_MARGIN, UUID_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    uuid_input.set_text_font(text_font);
+    uuid_input.set_text_size(FONT_SIZE);
+
+    let mut create_button = Button::new(
+        BUTTON_X,
+        PROFILE_BUTTON_Y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Create",
+    );
     create_button.set_label_font(text_font);
     create_button.set_label_size(FONT_SIZE);
     create_button.set_frame(FrameType::UpBox);
@@ -187,11 +272,20 @@ pub fn create_new_profile_dialog(text_font: Font) -> Option<Profile> {
 
     let username_clone = username_input.clone();
     let jvm_args_clone = jvm_args_input.clone();
+    let uuid_clone = uuid_input.clone();
     let mut win_clone = win.clone();
 
     create_button.set_callback(move |_| {
         let username = username_clone.value().trim().to_string();
         let jvm_args = jvm_args_clone.value().trim().to_string();
+        let uuid = match parse_uuid(&uuid_clone.value()) {
+            Ok(uuid) => uuid,
+            Err(_) => {
+                uuid_hint_label.set_label("Invalid UUID");
+                uuid_hint_label.set_label_color(ERROR_ICON_COLOR);
+                return;
+            }
+        };
 
         if !username.is_empty() {
             let jvm_args = if jvm_args.is_empty() {
@@ -199,7 +293,12 @@ pub fn create_new_profile_dialog(text_font: Font) -> Option<Profile> {
             } else {
                 Some(jvm_args)
             };
-            *result_clone.lock().unwrap() = Some(Profile { username, jvm_args });
+            *result_clone.lock().unwrap() = Some(Profile {
+                username,
+                jvm_args,
+                uuid,
+                account: None,
+            });
         }
         win_clone.hide();
         DIALOG_RUNNING.store(false, Ordering::SeqCst);
@@ -230,10 +329,321 @@ pub fn edit_profile_dialog(text_font: Font, profile: &Profile) -> Option<Profile
     }
 
     let mut win = Window::default()
-        .with_size(DIALOG_WIDTH, DIALOG_HEIGHT)
+        .with_size(DIALOG_WIDTH, PROFILE_DIALOG_HEIGHT)
         .with_label("Edit Profile");
     win.set_border(false);
 
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - DIALOG_WIDTH) / CENTER_DIVISOR,
+        (screen_height - PROFILE_DIALOG_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, PROFILE_DIALOG_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut username_label = Frame::new(
+        LEFT_MARGIN,
+        USERNAME_Y,
+        LABEL_WIDTH,
+        CONTROL_HEIGHT,
+        "Username:",
+    );
+    username_label.set_label_font(text_font);
+    username_label.set_label_size(FONT_SIZE);
+    username_label.set_align(Align::Left | Align::Inside);
+
+    let mut username_input = Input::new(LEFT_MARGIN, INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    username_input.set_text_font(text_font);
+    username_input.set_text_size(FONT_SIZE);
+    username_input.set_value(&profile.username);
+
+    let mut jvm_args_label = Frame::new(
+        LEFT_MARGIN,
+        JVM_ARGS_Y,
+        JVM_ARGS_WIDTH,
+        CONTROL_HEIGHT,
+        "JVM Arguments (optional):",
+    );
+    jvm_args_label.set_label_font(text_font);
+    jvm_args_label.set_label_size(FONT_SIZE);
+    jvm_args_label.set_align(Align::Left | Align::Inside);
+
+    let mut jvm_hint_label = Frame::new(
+        JVM_HINT_X,
+        JVM_ARGS_Y,
+        JVM_HINT_WIDTH,
+        CONTROL_HEIGHT,
+        "Example: -Xmx2G -Xms512M",
+    );
+    jvm_hint_label.set_label_font(text_font);
+    jvm_hint_label.set_label_size(SMALL_FONT_SIZE);
+    jvm_hint_label.set_label_color(HINT_TEXT_COLOR);
+    jvm_hint_label.set_align(Align::Left | Align::Inside);
+
+    let mut jvm_args_input = Input::new(LEFT_MARGIN, JVM_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    jvm_args_input.set_text_font(text_font);
+    jvm_args_input.set_text_size(FONT_SIZE);
+    if let Some(args) = &profile.jvm_args {
+        jvm_args_input.set_value(args);
+    }
+
+    let mut uuid_label = Frame::new(
+        LEFT_MARGIN,
+        UUID_LABEL_Y,
+        JVM_ARGS_WIDTH,
+        CONTROL_HEIGHT,
+        "UUID (optional):",
+    );
+    uuid_label.set_label_font(text_font);
+    uuid_label.set_label_size(FONT_SIZE);
+    uuid_label.set_align(Align::Left | Align::Inside);
+
+    let mut uuid_hint_label = Frame::new(
+        JVM_HINT_X,
+        UUID_LABEL_Y,
+        JVM_HINT_WIDTH,
+        CONTROL_HEIGHT,
+        "Blank = offline UUID",
+    );
+    uuid_hint_label.set_label_font(text_font);
+    uuid_hint_label.set_label_size(SMALL_FONT_SIZE);
+    uuid_hint_label.set_label_color(HINT_TEXT_COLOR);
+    uuid_hint_label.set_align(Align::Left | Align::Inside);
+
+    let mut uuid_input = Input::new(LEFT_MARGIN, UUID_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    uuid_input.set_text_font(text_font);
+    uuid_input.set_text_size(FONT_SIZE);
+    if let Some(uuid) = &profile.uuid {
+        uuid_input.set_value(uuid);
+    }
+
+    let mut save_button = Button::new(BUTTON_X, PROFILE_BUTTON_Y, BUTTON_X, CONTROL_HEIGHT, "Save");
+    save_button.set_label_font(text_font);
+    save_button.set_label_size(FONT_SIZE);
+    save_button.set_frame(FrameType::UpBox);
+    save_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Edit Profile",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    let result = Arc::new(Mutex::new(None::<Profile>));
+    let result_clone = result.clone();
+
+    let username_clone = username_input.clone();
+    let jvm_args_clone = jvm_args_input.clone();
+    let uuid_clone = uuid_input.clone();
+    let mut win_clone = win.clone();
+    let profile = profile.clone();
+
+    save_button.set_callback(move |_| {
+        let username = username_clone.value().trim().to_string();
+        let jvm_args = jvm_args_clone.value().trim().to_string();
+        let uuid = match parse_uuid(&uuid_clone.value()) {
+            Ok(uuid) => uuid,
+            Err(_) => {
+                uuid_hint_label.set_label("Invalid UUID");
+                uuid_hint_label.set_label_color(ERROR_ICON_COLOR);
+                return;
+            }
+        };
+
+        if !username.is_empty() {
+            let jvm_args = if jvm_args.is_empty() {
+                None
+            } else {
+                Some(jvm_args)
+            };
+            *result_clone.lock().unwrap() = Some(Profile {
+                username,
+                jvm_args,
+                uuid,
+                ..profile.clone()
+            });
+        }
+        win_clone.hide();
+        DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    });
+
+    win.set_callback(move |w| {
+        w.hide();
+        DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    while win.shown() {
+        app::wait();
+    }
+
+    DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    result.lock().unwrap().clone()
+}
+
+pub fn install_loader_dialog(
+    text_font: Font,
+    loader_name: &str,
+    game_version: &str,
+    loader_versions: &[String],
+    install: impl Fn(String) -> Result<String, String> + Send + Sync + 'static,
+) -> Option<String> {
+    if DIALOG_RUNNING.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+
+    let title = format!("Install {}", loader_name);
+    let mut win = Window::default()
+        .with_size(DIALOG_WIDTH, DIALOG_HEIGHT)
+        .with_label(&title);
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - DIALOG_WIDTH) / CENTER_DIVISOR,
+        (screen_height - DIALOG_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, DIALOG_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut game_version_label =
+        Frame::new(LEFT_MARGIN, USERNAME_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    game_version_label.set_label(&format!("Minecraft version: {}", game_version));
+    game_version_label.set_label_font(text_font);
+    game_version_label.set_label_size(FONT_SIZE);
+    game_version_label.set_align(Align::Left | Align::Inside);
+
+    let mut loader_label = Frame::new(
+        LEFT_MARGIN,
+        INPUT_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "Loader version:",
+    );
+    loader_label.set_label_font(text_font);
+    loader_label.set_label_size(FONT_SIZE);
+    loader_label.set_align(Align::Left | Align::Inside);
+
+    let mut loader_choice = Choice::new(LEFT_MARGIN, JVM_ARGS_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    loader_choice.set_color(Color::White);
+    loader_choice.set_text_font(text_font);
+    loader_choice.set_text_size(FONT_SIZE);
+    for version in loader_versions {
+        loader_choice.add_choice(version);
+    }
+    if !loader_versions.is_empty() {
+        loader_choice.set_value(0);
+    }
+
+    let mut status_label = Frame::new(LEFT_MARGIN, JVM_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    status_label.set_label_font(text_font);
+    status_label.set_label_size(SMALL_FONT_SIZE);
+    status_label.set_label_color(HINT_TEXT_COLOR);
+    status_label.set_align(Align::Left | Align::Inside);
+
+    let mut install_button = Button::new(BUTTON_X, BUTTON_Y, BUTTON_X, CONTROL_HEIGHT, "Install");
+    install_button.set_label_font(text_font);
+    install_button.set_label_size(FONT_SIZE);
+    install_button.set_frame(FrameType::UpBox);
+    install_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        &title,
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    let install_result = Arc::new(Mutex::new(None::<Result<String, String>>));
+    let install = Arc::new(install);
+
+    {
+        let install_result = install_result.clone();
+        let loader_choice = loader_choice.clone();
+        let mut status_label = status_label.clone();
+        install_button.set_callback(move |b| {
+            let loader_version = match loader_choice.choice() {
+                Some(version) => version,
+                None => return,
+            };
+
+            b.deactivate();
+            status_label.set_label("Installing...");
+
+            let install_result = install_result.clone();
+            let install = install.clone();
+            std::thread::spawn(move || {
+                let result = install(loader_version);
+                *install_result.lock().unwrap() = Some(result);
+                app::awake();
+            });
+        });
+    }
+
+    win.set_callback(move |w| {
+        w.hide();
+        DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    let mut installed_version = None;
+    while win.shown() {
+        app::wait();
+
+        if let Some(result) = install_result.lock().unwrap().take() {
+            match result {
+                Ok(version_id) => {
+                    installed_version = Some(version_id);
+                    win.hide();
+                }
+                Err(e) => {
+                    status_label.set_label(&format!("Install failed: {}", e));
+                    install_button.activate();
+                }
+            }
+        }
+    }
+
+    DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    installed_version
+}
+
+pub fn microsoft_login_dialog(
+    text_font: Font,
+    login: impl FnOnce(&dyn Fn(&str, &str)) -> Result<MicrosoftAccount, String> + Send + 'static,
+) -> Option<Result<MicrosoftAccount, String>> {
+    if DIALOG_RUNNING.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+
+    let mut win = Window::default()
+        .with_size(DIALOG_WIDTH, DIALOG_HEIGHT)
+        .with_label("Microsoft Login");
+    win.set_border(false);
+
     let screen_width = app::screen_size().0 as i32;
     let screen_height = app::screen_size().1 as i32;
     win.set_pos(
@@ -241,65 +651,1395 @@ pub fn edit_profile_dialog(text_font: Font, profile: &Profile) -> Option<Profile
         (screen_height - DIALOG_HEIGHT) / CENTER_DIVISOR,
     );
 
-    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, DIALOG_HEIGHT, "");
-    bg.set_frame(FrameType::FlatBox);
-    bg.set_color(GRAY_COLOR);
+    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, DIALOG_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut uri_label = Frame::new(
+        LEFT_MARGIN,
+        USERNAME_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "Open this page in your browser:",
+    );
+    uri_label.set_label_font(text_font);
+    uri_label.set_label_size(FONT_SIZE);
+    uri_label.set_align(Align::Left | Align::Inside);
+
+    let mut uri_input = Input::new(LEFT_MARGIN, INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    uri_input.set_text_font(text_font);
+    uri_input.set_text_size(FONT_SIZE);
+    uri_input.set_value("Requesting login code...");
+    uri_input.set_readonly(true);
+
+    let mut code_label = Frame::new(
+        LEFT_MARGIN,
+        JVM_ARGS_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "and enter this code:",
+    );
+    code_label.set_label_font(text_font);
+    code_label.set_label_size(FONT_SIZE);
+    code_label.set_align(Align::Left | Align::Inside);
+
+    let mut code_input = Input::new(LEFT_MARGIN, JVM_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    code_input.set_text_font(text_font);
+    code_input.set_text_size(FONT_SIZE);
+    code_input.set_readonly(true);
+
+    let mut open_button = Button::new(BUTTON_X, BUTTON_Y, BUTTON_X, CONTROL_HEIGHT, "Open Browser");
+    open_button.set_label_font(text_font);
+    open_button.set_label_size(FONT_SIZE);
+    open_button.set_frame(FrameType::UpBox);
+    open_button.set_color(GRAY_COLOR);
+    open_button.deactivate();
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Microsoft Login",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    {
+        let uri_input = uri_input.clone();
+        open_button.set_callback(move |_| {
+            let verification_uri = uri_input.value();
+
+            #[cfg(target_os = "windows")]
+            {
+                let _ = Command::new("explorer").arg(&verification_uri).spawn();
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                let _ = Command::new("open").arg(&verification_uri).spawn();
+            }
+
+            #[cfg(all(unix, not(target_os = "macos")))]
+            {
+                let _ = Command::new("xdg-open").arg(&verification_uri).spawn();
+            }
+        });
+    }
+
+    let device_code = Arc::new(Mutex::new(None::<(String, String)>));
+    let login_result = Arc::new(Mutex::new(None::<Result<MicrosoftAccount, String>>));
+
+    {
+        let device_code = device_code.clone();
+        let login_result = login_result.clone();
+        std::thread::spawn(move || {
+            let result = login(&|verification_uri, user_code| {
+                *device_code.lock().unwrap() =
+                    Some((verification_uri.to_string(), user_code.to_string()));
+                app::awake();
+            });
+            *login_result.lock().unwrap() = Some(result);
+            app::awake();
+        });
+    }
+
+    win.set_callback(move |w| {
+        w.hide();
+        DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    let mut result = None;
+    while win.shown() {
+        app::wait();
+
+        if let Some((verification_uri, user_code)) = device_code.lock().unwrap().take() {
+            uri_input.set_value(&verification_uri);
+            code_input.set_value(&user_code);
+            open_button.activate();
+        }
+
+        if let Some(login) = login_result.lock().unwrap().take() {
+            result = Some(login);
+            win.hide();
+        }
+    }
+
+    DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+pub struct AuthServerLogin {
+    pub server_url: String,
+    pub username: String,
+    pub password: String,
+}
+
+pub fn auth_server_login_dialog<T: Send + 'static>(
+    text_font: Font,
+    login: impl Fn(AuthServerLogin) -> Result<T, String> + Send + Sync + 'static,
+) -> Option<T> {
+    if DIALOG_RUNNING.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+
+    let mut win = Window::default()
+        .with_size(DIALOG_WIDTH, AUTH_DIALOG_HEIGHT)
+        .with_label("Auth Server Login");
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - DIALOG_WIDTH) / CENTER_DIVISOR,
+        (screen_height - AUTH_DIALOG_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, DIALOG_WIDTH, AUTH_DIALOG_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut server_label = Frame::new(
+        LEFT_MARGIN,
+        USERNAME_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "Auth server URL:",
+    );
+    server_label.set_label_font(text_font);
+    server_label.set_label_size(FONT_SIZE);
+    server_label.set_align(Align::Left | Align::Inside);
+
+    let mut server_input = Input::new(LEFT_MARGIN, INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    server_input.set_text_font(text_font);
+    server_input.set_text_size(FONT_SIZE);
+
+    let mut username_label = Frame::new(
+        LEFT_MARGIN,
+        JVM_ARGS_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "Username or email:",
+    );
+    username_label.set_label_font(text_font);
+    username_label.set_label_size(FONT_SIZE);
+    username_label.set_align(Align::Left | Align::Inside);
+
+    let mut username_input = Input::new(LEFT_MARGIN, JVM_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    username_input.set_text_font(text_font);
+    username_input.set_text_size(FONT_SIZE);
+
+    let mut password_label = Frame::new(
+        LEFT_MARGIN,
+        PASSWORD_LABEL_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "Password:",
+    );
+    password_label.set_label_font(text_font);
+    password_label.set_label_size(FONT_SIZE);
+    password_label.set_align(Align::Left | Align::Inside);
+
+    let mut password_input = SecretInput::new(
+        LEFT_MARGIN,
+        PASSWORD_INPUT_Y,
+        INPUT_WIDTH,
+        CONTROL_HEIGHT,
+        "",
+    );
+    password_input.set_text_font(text_font);
+    password_input.set_text_size(FONT_SIZE);
+
+    let mut status_label = Frame::new(LEFT_MARGIN, AUTH_STATUS_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
+    status_label.set_label_font(text_font);
+    status_label.set_label_size(SMALL_FONT_SIZE);
+    status_label.set_label_color(HINT_TEXT_COLOR);
+    status_label.set_align(Align::Left | Align::Inside);
+
+    let mut login_button = Button::new(BUTTON_X, AUTH_BUTTON_Y, BUTTON_X, CONTROL_HEIGHT, "Login");
+    login_button.set_label_font(text_font);
+    login_button.set_label_size(FONT_SIZE);
+    login_button.set_frame(FrameType::UpBox);
+    login_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Auth Server Login",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    let login_result = Arc::new(Mutex::new(None::<Result<T, String>>));
+    let login = Arc::new(login);
+
+    {
+        let login_result = login_result.clone();
+        let mut status_label = status_label.clone();
+        login_button.set_callback(move |b| {
+            let server_url = server_input.value().trim().to_string();
+            let username = username_input.value().trim().to_string();
+            let password = password_input.value();
+
+            if server_url.is_empty() || username.is_empty() {
+                status_label.set_label("Enter the auth server URL and username");
+                return;
+            }
+
+            b.deactivate();
+            status_label.set_label("Logging in...");
+
+            let login_result = login_result.clone();
+            let login = login.clone();
+            std::thread::spawn(move || {
+                let result = login(AuthServerLogin {
+                    server_url,
+                    username,
+                    password,
+                });
+                *login_result.lock().unwrap() = Some(result);
+                app::awake();
+            });
+        });
+    }
+
+    win.set_callback(move |w| {
+        w.hide();
+        DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    let mut account = None;
+    while win.shown() {
+        app::wait();
+
+        if let Some(result) = login_result.lock().unwrap().take() {
+            match result {
+                Ok(logged_in) => {
+                    account = Some(logged_in);
+                    win.hide();
+                }
+                Err(e) => {
+                    status_label.set_label(&format!("Login failed: {}", e));
+                    login_button.activate();
+                }
+            }
+        }
+    }
+
+    DIALOG_RUNNING.store(false, Ordering::SeqCst);
+    account
+}
+
+pub struct ConsoleLog {
+    lines: VecDeque<LogLine>,
+    revision: u64,
+}
+
+impl ConsoleLog {
+    const fn new() -> Self {
+        ConsoleLog {
+            lines: VecDeque::new(),
+            revision: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.revision += 1;
+    }
+
+    pub fn push(&mut self, line: LogLine) {
+        if self.lines.len() == MAX_CONSOLE_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.revision += 1;
+    }
+}
+
+pub static GAME_LOG: Mutex<ConsoleLog> = Mutex::new(ConsoleLog::new());
+static CONSOLE_OPEN: AtomicBool = AtomicBool::new(false);
+static INSTANCES_OPEN: AtomicBool = AtomicBool::new(false);
+static DOWNLOADS_OPEN: AtomicBool = AtomicBool::new(false);
+static MANAGER_OPEN: AtomicBool = AtomicBool::new(false);
+static VERIFY_OPEN: AtomicBool = AtomicBool::new(false);
+static STORAGE_OPEN: AtomicBool = AtomicBool::new(false);
+
+pub fn log_console_window(text_font: Font) {
+    if CONSOLE_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut win = Window::default()
+        .with_size(CONSOLE_WIDTH, CONSOLE_HEIGHT)
+        .with_label("Log Console");
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - CONSOLE_WIDTH) / CENTER_DIVISOR,
+        (screen_height - CONSOLE_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, CONSOLE_WIDTH, CONSOLE_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut search_label = Frame::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        JAVA_LABEL_WIDTH,
+        CONTROL_HEIGHT,
+        "Find:",
+    );
+    search_label.set_label_font(text_font);
+    search_label.set_label_size(FONT_SIZE);
+    search_label.set_align(Align::Left | Align::Inside);
+
+    let mut search_input = Input::new(
+        LEFT_MARGIN + JAVA_LABEL_WIDTH,
+        TOP_MARGIN,
+        CONSOLE_SEARCH_WIDTH,
+        CONTROL_HEIGHT,
+        "",
+    );
+    search_input.set_text_font(text_font);
+    search_input.set_text_size(FONT_SIZE);
+
+    let mut level_label = Frame::new(
+        CONSOLE_LEVEL_X,
+        TOP_MARGIN,
+        JAVA_LABEL_WIDTH,
+        CONTROL_HEIGHT,
+        "Level:",
+    );
+    level_label.set_label_font(text_font);
+    level_label.set_label_size(FONT_SIZE);
+    level_label.set_align(Align::Left | Align::Inside);
+
+    let mut level_choice = Choice::new(
+        CONSOLE_LEVEL_X + JAVA_LABEL_WIDTH,
+        TOP_MARGIN,
+        CONSOLE_LEVEL_WIDTH,
+        CONTROL_HEIGHT,
+        "",
+    );
+    level_choice.set_color(Color::White);
+    level_choice.set_text_font(text_font);
+    level_choice.set_text_size(FONT_SIZE);
+    level_choice.add_choice("All|Info|Warnings|Errors");
+    level_choice.set_value(0);
+
+    let mut buffer = TextBuffer::default();
+    let mut display = TextDisplay::new(
+        LEFT_MARGIN,
+        TOP_MARGIN + CONTROL_HEIGHT + BUTTON_SPACING,
+        CONSOLE_WIDTH - LEFT_MARGIN * 2,
+        CONSOLE_HEIGHT - TOP_MARGIN - CONTROL_HEIGHT - BUTTON_SPACING - LEFT_MARGIN,
+        "",
+    );
+    display.set_buffer(buffer.clone());
+    display.set_frame(FrameType::DownBox);
+    display.set_text_font(Font::Courier);
+    display.set_text_size(SMALL_FONT_SIZE);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Log Console",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    win.set_callback(move |w| {
+        w.hide();
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    let mut rendered = None;
+    app::add_timeout3(0.0, move |handle| {
+        if !win.shown() {
+            CONSOLE_OPEN.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        let search = search_input.value().to_lowercase();
+        let min_level = match level_choice.value() {
+            1 => LogLevel::Info,
+            2 => LogLevel::Warn,
+            3 => LogLevel::Error,
+            _ => LogLevel::Debug,
+        };
+
+        let log = GAME_LOG.lock().unwrap();
+        let state = (log.revision, search.clone(), min_level);
+        if rendered.as_ref() != Some(&state) {
+            let text: Vec<&str> = log
+                .lines
+                .iter()
+                .filter(|line| line.level >= min_level)
+                .filter(|line| {
+                    search.is_empty()
+                        || line.text.to_lowercase().contains(&search)
+                        || line
+                            .event
+                            .as_ref()
+                            .is_some_and(|event| event.logger.to_lowercase().contains(&search))
+                })
+                .map(|line| line.text.as_str())
+                .collect();
+            buffer.set_text(&text.join("\n"));
+            display.set_insert_position(buffer.length());
+            display.show_insert_position();
+            rendered = Some(state);
+        }
+        drop(log);
+
+        app::repeat_timeout3(CONSOLE_REFRESH_SECS, handle);
+    });
+}
+
+pub fn running_instances_window(text_font: Font, registry: Arc<ProcessRegistry>) {
+    if INSTANCES_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut win = Window::default()
+        .with_size(INSTANCES_WIDTH, INSTANCES_HEIGHT)
+        .with_label("Running Instances");
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - INSTANCES_WIDTH) / CENTER_DIVISOR,
+        (screen_height - INSTANCES_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, INSTANCES_WIDTH, INSTANCES_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let buttons_y = INSTANCES_HEIGHT - BUTTONS_MARGIN;
+
+    let mut browser = HoldBrowser::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        INSTANCES_WIDTH - LEFT_MARGIN * 2,
+        buttons_y - TOP_MARGIN - LABEL_HEIGHT - BUTTON_SPACING,
+        "",
+    );
+    browser.set_frame(FrameType::DownBox);
+    browser.set_text_size(FONT_SIZE);
+    browser.set_column_widths(&[120, 150, 70]);
+    browser.set_column_char('\t');
+
+    let mut play_time_label = Frame::new(
+        LEFT_MARGIN,
+        buttons_y - LABEL_HEIGHT - BUTTON_SPACING / 2,
+        INSTANCES_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "",
+    );
+    play_time_label.set_label_font(text_font);
+    play_time_label.set_label_size(FONT_SIZE);
+    play_time_label.set_align(Align::Left | Align::Inside);
+
+    let mut kill_button = Button::new(
+        INSTANCES_WIDTH - LEFT_MARGIN - BUTTON_X * 2 - BUTTON_SPACING,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Kill Game",
+    );
+    kill_button.set_label_font(text_font);
+    kill_button.set_label_size(FONT_SIZE);
+    kill_button.set_frame(FrameType::UpBox);
+    kill_button.set_color(GRAY_COLOR);
+
+    let mut close_button = Button::new(
+        INSTANCES_WIDTH - LEFT_MARGIN - BUTTON_X,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Close",
+    );
+    close_button.set_label_font(text_font);
+    close_button.set_label_size(FONT_SIZE);
+    close_button.set_frame(FrameType::UpBox);
+    close_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Running Instances",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    let pids = Rc::new(RefCell::new(Vec::<u32>::new()));
+
+    kill_button.set_callback({
+        let registry = registry.clone();
+        let pids = pids.clone();
+        let browser = browser.clone();
+        move |_| {
+            let pid = match browser.value() {
+                0 => None,
+                line => pids.borrow().get(line as usize - 1).copied(),
+            };
+            match pid {
+                Some(pid) => {
+                    if let Err(e) = registry.kill(pid) {
+                        show_error_dialog(&format!("Failed to stop the game: {}", e), text_font);
+                    }
+                }
+                None => show_error_dialog("Select a running game to stop", text_font),
+            }
+        }
+    });
+
+    let mut win_clone = win.clone();
+    close_button.set_callback(move |_| {
+        win_clone.hide();
+    });
+
+    win.set_callback(move |w| {
+        w.hide();
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    app::add_timeout3(0.0, move |handle| {
+        if !win.shown() {
+            INSTANCES_OPEN.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        let selected = match browser.value() {
+            0 => None,
+            line => pids.borrow().get(line as usize - 1).copied(),
+        };
+
+        let instances = registry.running();
+        browser.clear();
+        for instance in &instances {
+            let elapsed = SystemTime::now()
+                .duration_since(instance.started_at)
+                .unwrap_or_default()
+                .as_secs();
+            browser.add(&format!(
+                "{}\t{}\t{}\t{}",
+                instance.profile,
+                instance.version,
+                instance.pid,
+                format_duration(elapsed)
+            ));
+        }
+        if instances.is_empty() {
+            browser.add("No game is running");
+        }
+
+        let selected_index =
+            selected.and_then(|pid| instances.iter().position(|instance| instance.pid == pid));
+        if let Some(index) = selected_index {
+            browser.select(index as i32 + 1);
+        }
+        *pids.borrow_mut() = instances.iter().map(|instance| instance.pid).collect();
+
+        let stats = read_stats(registry.stats_path()).unwrap_or_default();
+        let label = match selected_index.map(|index| &instances[index].profile) {
+            Some(profile) => format!(
+                "Play time for {}: {}",
+                profile,
+                format_duration(stats.get(profile).map_or(0, |stats| stats.play_time_secs))
+            ),
+            None => format!(
+                "Total play time: {}",
+                format_duration(stats.values().map(|stats| stats.play_time_secs).sum())
+            ),
+        };
+        play_time_label.set_label(&label);
+
+        app::repeat_timeout3(INSTANCES_REFRESH_SECS, handle);
+    });
+}
+
+pub fn background_downloads_window(text_font: Font, background_jobs: Arc<BackgroundJobs>) {
+    if DOWNLOADS_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut win = Window::default()
+        .with_size(DOWNLOADS_WIDTH, DOWNLOADS_HEIGHT)
+        .with_label("Background Downloads");
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - DOWNLOADS_WIDTH) / CENTER_DIVISOR,
+        (screen_height - DOWNLOADS_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, DOWNLOADS_WIDTH, DOWNLOADS_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut jobs_browser = HoldBrowser::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        DOWNLOADS_WIDTH - LEFT_MARGIN * 2,
+        DOWNLOADS_JOBS_HEIGHT,
+        "",
+    );
+    jobs_browser.set_frame(FrameType::DownBox);
+    jobs_browser.set_text_size(FONT_SIZE);
+    jobs_browser.set_column_widths(&[150, 110]);
+    jobs_browser.set_column_char('\t');
+
+    let status_y = TOP_MARGIN + DOWNLOADS_JOBS_HEIGHT + BUTTON_SPACING / 2;
+    let mut status_label = Frame::new(
+        LEFT_MARGIN,
+        status_y,
+        DOWNLOADS_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "",
+    );
+    status_label.set_label_font(text_font);
+    status_label.set_label_size(FONT_SIZE);
+    status_label.set_align(Align::Left | Align::Inside);
+
+    let mut failures_label = Frame::new(
+        LEFT_MARGIN,
+        status_y + LABEL_HEIGHT,
+        DOWNLOADS_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "Failed files:",
+    );
+    failures_label.set_label_font(text_font);
+    failures_label.set_label_size(FONT_SIZE);
+    failures_label.set_align(Align::Left | Align::Inside);
+
+    let buttons_y = DOWNLOADS_HEIGHT - BUTTONS_MARGIN;
+    let failures_y = status_y + LABEL_HEIGHT * 2;
+
+    let mut failures_browser = HoldBrowser::new(
+        LEFT_MARGIN,
+        failures_y,
+        DOWNLOADS_WIDTH - LEFT_MARGIN * 2,
+        buttons_y - failures_y - BUTTON_SPACING,
+        "",
+    );
+    failures_browser.set_frame(FrameType::DownBox);
+    failures_browser.set_text_size(SMALL_FONT_SIZE);
+
+    let mut retry_button = Button::new(
+        DOWNLOADS_WIDTH - LEFT_MARGIN - BUTTON_X * 2 - BUTTON_SPACING,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Retry Failed",
+    );
+    retry_button.set_label_font(text_font);
+    retry_button.set_label_size(FONT_SIZE);
+    retry_button.set_frame(FrameType::UpBox);
+    retry_button.set_color(GRAY_COLOR);
+
+    let mut close_button = Button::new(
+        DOWNLOADS_WIDTH - LEFT_MARGIN - BUTTON_X,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Close",
+    );
+    close_button.set_label_font(text_font);
+    close_button.set_label_size(FONT_SIZE);
+    close_button.set_frame(FrameType::UpBox);
+    close_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Background Downloads",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    retry_button.set_callback({
+        let background_jobs = background_jobs.clone();
+        let jobs_browser = jobs_browser.clone();
+        move |_| {
+            let jobs = background_jobs.list();
+            let selected = jobs_browser.value().max(1) as usize - 1;
+            if let Some(job) = jobs.get(selected)
+                && !job.retry()
+            {
+                show_error_dialog("There are no failed downloads to retry", text_font);
+            }
+        }
+    });
+
+    let mut win_clone = win.clone();
+    close_button.set_callback(move |_| {
+        win_clone.hide();
+    });
+
+    win.set_callback(move |w| {
+        w.hide();
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    let mut rendered_failures = None;
+    app::add_timeout3(0.0, move |handle| {
+        if !win.shown() {
+            DOWNLOADS_OPEN.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        let jobs = background_jobs.list();
+        let selected = jobs_browser.value().max(1) as usize - 1;
+
+        jobs_browser.clear();
+        for job in &jobs {
+            let status = job.status();
+            let state = if status.running {
+                "Downloading".to_string()
+            } else if !status.failures.is_empty() {
+                format!("{} failed", status.failures.len())
+            } else if status.cancelled {
+                "Cancelled".to_string()
+            } else {
+                "Complete".to_string()
+            };
+            jobs_browser.add(&format!(
+                "{}\t{}/{}\t{}",
+                job.name(),
+                status.completed,
+                status.total,
+                state
+            ));
+        }
+        if jobs.is_empty() {
+            jobs_browser.add("No background downloads");
+        }
+
+        let status = jobs.get(selected).map(|job| job.status());
+        if status.is_some() {
+            jobs_browser.select(selected as i32 + 1);
+        }
+
+        let label = match &status {
+            Some(status) if status.running && status.bytes.total > 0 => status.bytes.format(),
+            Some(status) => format!("{} of {} files downloaded", status.completed, status.total),
+            None => String::new(),
+        };
+        status_label.set_label(&label);
+        retry_button.activate();
+        if status
+            .as_ref()
+            .is_none_or(|status| status.running || status.failures.is_empty())
+        {
+            retry_button.deactivate();
+        }
+
+        let failures: Vec<String> = status
+            .map(|status| {
+                status
+                    .failures
+                    .iter()
+                    .map(|failure| format!("{}: {}", failure.label, failure.error))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if rendered_failures.as_ref() != Some(&failures) {
+            failures_browser.clear();
+            for failure in &failures {
+                failures_browser.add(failure);
+            }
+            rendered_failures = Some(failures);
+        }
+
+        app::repeat_timeout3(DOWNLOADS_REFRESH_SECS, handle);
+    });
+}
+
+pub fn download_manager_window(text_font: Font, install_queue: Arc<InstallQueue>) {
+    if MANAGER_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut win = Window::default()
+        .with_size(MANAGER_WIDTH, MANAGER_HEIGHT)
+        .with_label("Download Manager");
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - MANAGER_WIDTH) / CENTER_DIVISOR,
+        (screen_height - MANAGER_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, MANAGER_WIDTH, MANAGER_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let buttons_y = MANAGER_HEIGHT - BUTTONS_MARGIN;
+    let status_y = buttons_y - BUTTON_SPACING - LABEL_HEIGHT;
+
+    let mut entries_browser = HoldBrowser::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        MANAGER_WIDTH - LEFT_MARGIN * 2,
+        status_y - TOP_MARGIN - BUTTON_SPACING / 2,
+        "",
+    );
+    entries_browser.set_frame(FrameType::DownBox);
+    entries_browser.set_text_size(FONT_SIZE);
+    entries_browser.set_column_widths(&[170, 90]);
+    entries_browser.set_column_char('\t');
+
+    let mut status_label = Frame::new(
+        LEFT_MARGIN,
+        status_y,
+        MANAGER_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "",
+    );
+    status_label.set_label_font(text_font);
+    status_label.set_label_size(FONT_SIZE);
+    status_label.set_align(Align::Left | Align::Inside);
+
+    let mut pause_button = Button::new(
+        MANAGER_WIDTH - LEFT_MARGIN - BUTTON_X * 4 - BUTTON_SPACING * 3,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Pause",
+    );
+    pause_button.set_label_font(text_font);
+    pause_button.set_label_size(FONT_SIZE);
+    pause_button.set_frame(FrameType::UpBox);
+    pause_button.set_color(GRAY_COLOR);
+
+    let mut retry_button = Button::new(
+        MANAGER_WIDTH - LEFT_MARGIN - BUTTON_X * 3 - BUTTON_SPACING * 2,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Retry",
+    );
+    retry_button.set_label_font(text_font);
+    retry_button.set_label_size(FONT_SIZE);
+    retry_button.set_frame(FrameType::UpBox);
+    retry_button.set_color(GRAY_COLOR);
+
+    let mut cancel_button = Button::new(
+        MANAGER_WIDTH - LEFT_MARGIN - BUTTON_X * 2 - BUTTON_SPACING,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Cancel",
+    );
+    cancel_button.set_label_font(text_font);
+    cancel_button.set_label_size(FONT_SIZE);
+    cancel_button.set_frame(FrameType::UpBox);
+    cancel_button.set_color(GRAY_COLOR);
+
+    let mut close_button = Button::new(
+        MANAGER_WIDTH - LEFT_MARGIN - BUTTON_X,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Close",
+    );
+    close_button.set_label_font(text_font);
+    close_button.set_label_size(FONT_SIZE);
+    close_button.set_frame(FrameType::UpBox);
+    close_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Download Manager",
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    let selected_entry = {
+        let install_queue = install_queue.clone();
+        let entries_browser = entries_browser.clone();
+        move || {
+            let selected = entries_browser.value().max(1) as usize - 1;
+            install_queue.entries().get(selected).cloned()
+        }
+    };
+
+    pause_button.set_callback({
+        let install_queue = install_queue.clone();
+        let selected_entry = selected_entry.clone();
+        move |_| {
+            if let Some(entry) = selected_entry() {
+                if entry.state == InstallState::Paused {
+                    install_queue.resume(entry.id);
+                } else {
+                    install_queue.pause(entry.id);
+                }
+            }
+        }
+    });
+
+    retry_button.set_callback({
+        let install_queue = install_queue.clone();
+        let selected_entry = selected_entry.clone();
+        move |_| {
+            if let Some(entry) = selected_entry() {
+                install_queue.retry(entry.id);
+            }
+        }
+    });
+
+    cancel_button.set_callback({
+        let install_queue = install_queue.clone();
+        let selected_entry = selected_entry.clone();
+        move |_| {
+            if let Some(entry) = selected_entry() {
+                install_queue.cancel(entry.id);
+            }
+        }
+    });
+
+    let mut win_clone = win.clone();
+    close_button.set_callback(move |_| {
+        win_clone.hide();
+    });
+
+    win.set_callback(move |w| {
+        w.hide();
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    app::add_timeout3(0.0, move |handle| {
+        if !win.shown() {
+            MANAGER_OPEN.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        let entries = install_queue.entries();
+        let selected = entries_browser.value().max(1) as usize - 1;
+
+        entries_browser.clear();
+        for entry in &entries {
+            let percentage = match (&entry.progress, entry.state) {
+                (_, InstallState::Complete) => "100%".to_string(),
+                (Some(progress), _) => format!("{:.0}%", progress.percentage()),
+                (None, _) => String::new(),
+            };
+            entries_browser.add(&format!(
+                "{}\t{}\t{}",
+                entry.version_id,
+                entry.state.name(),
+                percentage
+            ));
+        }
+        if entries.is_empty() {
+            entries_browser.add("No installs queued");
+        }
+
+        let entry = entries.get(selected);
+        if entry.is_some() {
+            entries_browser.select(selected as i32 + 1);
+        }
+
+        let label = match entry {
+            Some(entry) if entry.error.is_some() => entry.error.clone().unwrap_or_default(),
+            Some(entry) if entry.state.is_finished() || entry.state == InstallState::Paused => {
+                format!("{} {}", entry.version_id, entry.state.name().to_lowercase())
+            }
+            Some(entry) => match &entry.progress {
+                Some(progress) => match &progress.bytes {
+                    Some(bytes) if bytes.total > 0 => {
+                        format!("{} - {}", bytes.format(), progress.message)
+                    }
+                    _ => progress.message.clone(),
+                },
+                None => format!("Waiting to install {}", entry.version_id),
+            },
+            None => String::new(),
+        };
+        status_label.set_label(&label);
+
+        let state = entry.map(|entry| entry.state);
+        pause_button.set_label(if state == Some(InstallState::Paused) {
+            "Resume"
+        } else {
+            "Pause"
+        });
+        for (button, enabled) in [
+            (
+                &mut pause_button,
+                state.is_some_and(|state| !state.is_finished()),
+            ),
+            (
+                &mut retry_button,
+                matches!(state, Some(InstallState::Failed | InstallState::Cancelled)),
+            ),
+            (
+                &mut cancel_button,
+                state.is_some_and(|state| !state.is_finished()),
+            ),
+        ] {
+            if enabled {
+                button.activate();
+            } else {
+                button.deactivate();
+            }
+        }
+
+        app::repeat_timeout3(DOWNLOADS_REFRESH_SECS, handle);
+    });
+}
+
+#[derive(Default)]
+pub struct VerifyState {
+    pub running: bool,
+    pub revision: u64,
+    pub progress: Option<LaunchProgress>,
+    pub report: Option<VerifyReport>,
+    pub error: Option<String>,
+}
+
+pub fn verify_window(
+    text_font: Font,
+    version_id: &str,
+    repair: bool,
+    state: Arc<Mutex<VerifyState>>,
+    run_check: impl Fn(bool) + 'static,
+) {
+    if VERIFY_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let title = format!("Verify {}", version_id);
+    let mut win = Window::default()
+        .with_size(VERIFY_WIDTH, VERIFY_HEIGHT)
+        .with_label(&title);
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - VERIFY_WIDTH) / CENTER_DIVISOR,
+        (screen_height - VERIFY_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, VERIFY_WIDTH, VERIFY_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let buttons_y = VERIFY_HEIGHT - BUTTONS_MARGIN;
+    let status_y = buttons_y - BUTTON_SPACING - LABEL_HEIGHT;
+
+    let mut issues_browser = HoldBrowser::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        VERIFY_WIDTH - LEFT_MARGIN * 2,
+        status_y - TOP_MARGIN - BUTTON_SPACING / 2,
+        "",
+    );
+    issues_browser.set_frame(FrameType::DownBox);
+    issues_browser.set_text_size(SMALL_FONT_SIZE);
+    issues_browser.set_column_widths(&[VERIFY_KIND_WIDTH]);
+    issues_browser.set_column_char('\t');
+
+    let mut status_label = Frame::new(
+        LEFT_MARGIN,
+        status_y,
+        VERIFY_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "",
+    );
+    status_label.set_label_font(text_font);
+    status_label.set_label_size(FONT_SIZE);
+    status_label.set_align(Align::Left | Align::Inside);
+
+    let mut repair_button = Button::new(
+        VERIFY_WIDTH - LEFT_MARGIN - BUTTON_X * 2 - BUTTON_SPACING,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Repair",
+    );
+    repair_button.set_label_font(text_font);
+    repair_button.set_label_size(FONT_SIZE);
+    repair_button.set_frame(FrameType::UpBox);
+    repair_button.set_color(GRAY_COLOR);
+
+    let mut close_button = Button::new(
+        VERIFY_WIDTH - LEFT_MARGIN - BUTTON_X,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Close",
+    );
+    close_button.set_label_font(text_font);
+    close_button.set_label_size(FONT_SIZE);
+    close_button.set_frame(FrameType::UpBox);
+    close_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        &title,
+        &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    let run_check = Rc::new(run_check);
+    repair_button.set_callback({
+        let run_check = run_check.clone();
+        move |_| run_check(true)
+    });
+
+    let mut win_clone = win.clone();
+    close_button.set_callback(move |_| {
+        win_clone.hide();
+    });
+
+    win.set_callback(move |w| {
+        w.hide();
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    run_check(repair);
+
+    let mut rendered_revision = None;
+    app::add_timeout3(0.0, move |handle| {
+        if !win.shown() {
+            VERIFY_OPEN.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        let state = state.lock().unwrap();
+        if rendered_revision != Some(state.revision) {
+            issues_browser.clear();
+            for issue in state.report.iter().flat_map(|report| &report.issues) {
+                issues_browser.add(&format!("{}\t{}", issue.kind.name(), issue.path.display()));
+            }
+            rendered_revision = Some(state.revision);
+        }
+
+        let label = match (&state.progress, &state.report, &state.error) {
+            (Some(progress), _, _) if state.running => progress.message.clone(),
+            (_, _, _) if state.running => "Verifying...".to_string(),
+            (_, _, Some(error)) => error.clone(),
+            (_, Some(report), _) => report.summary(),
+            _ => String::new(),
+        };
+        status_label.set_label(&label);
+
+        if !state.running && state.report.as_ref().is_some_and(|report| !report.is_ok()) {
+            repair_button.activate();
+        } else {
+            repair_button.deactivate();
+        }
+
+        app::repeat_timeout3(DOWNLOADS_REFRESH_SECS, handle);
+    });
+}
+
+pub enum StorageAction {
+    Refresh,
+    FindUnused,
+    DeleteUnused,
+    RemoveVersion(String),
+}
+
+#[derive(Default)]
+pub struct StorageState {
+    pub running: bool,
+    pub revision: u64,
+    pub usage: Option<DiskUsage>,
+    pub plan: Option<GcPlan>,
+    pub message: String,
+}
+
+pub fn storage_window(
+    text_font: Font,
+    state: Arc<Mutex<StorageState>>,
+    run_action: impl Fn(StorageAction) + 'static,
+) {
+    if STORAGE_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut win = Window::default()
+        .with_size(STORAGE_WIDTH, STORAGE_HEIGHT)
+        .with_label("Storage Manager");
+    win.set_border(false);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - STORAGE_WIDTH) / CENTER_DIVISOR,
+        (screen_height - STORAGE_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, STORAGE_WIDTH, STORAGE_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut usage_browser = HoldBrowser::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        STORAGE_WIDTH - LEFT_MARGIN * 2,
+        STORAGE_USAGE_HEIGHT,
+        "",
+    );
+    usage_browser.set_frame(FrameType::DownBox);
+    usage_browser.set_text_size(FONT_SIZE);
+    usage_browser.set_column_widths(&[200]);
+    usage_browser.set_column_char('\t');
+
+    let unused_y = TOP_MARGIN + STORAGE_USAGE_HEIGHT + BUTTON_SPACING / 2;
+    let mut unused_label = Frame::new(
+        LEFT_MARGIN,
+        unused_y,
+        STORAGE_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "Unused files:",
+    );
+    unused_label.set_label_font(text_font);
+    unused_label.set_label_size(FONT_SIZE);
+    unused_label.set_align(Align::Left | Align::Inside);
+
+    let buttons_y = STORAGE_HEIGHT - BUTTONS_MARGIN;
+    let status_y = buttons_y - BUTTON_SPACING - LABEL_HEIGHT;
 
-    let mut username_label = Frame::new(
+    let mut unused_browser = HoldBrowser::new(
         LEFT_MARGIN,
-        USERNAME_Y,
-        LABEL_WIDTH,
-        CONTROL_HEIGHT,
-        "Username:",
+        unused_y + LABEL_HEIGHT,
+        STORAGE_WIDTH - LEFT_MARGIN * 2,
+        status_y - unused_y - LABEL_HEIGHT - BUTTON_SPACING / 2,
+        "",
     );
-    username_label.set_label_font(text_font);
-    username_label.set_label_size(FONT_SIZE);
-    username_label.set_align(Align::Left | Align::Inside);
-
-    let mut username_input = Input::new(LEFT_MARGIN, INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
-    username_input.set_text_font(text_font);
-    username_input.set_text_size(FONT_SIZE);
-    username_input.set_value(&profile.username);
+    unused_browser.set_frame(FrameType::DownBox);
+    unused_browser.set_text_size(SMALL_FONT_SIZE);
+    unused_browser.set_column_widths(&[90, 70]);
+    unused_browser.set_column_char('\t');
 
-    let mut jvm_args_label = Frame::new(
+    let mut status_label = Frame::new(
         LEFT_MARGIN,
-        JVM_ARGS_Y,
-        JVM_ARGS_WIDTH,
+        status_y,
+        STORAGE_WIDTH - LEFT_MARGIN * 2,
+        LABEL_HEIGHT,
+        "",
+    );
+    status_label.set_label_font(text_font);
+    status_label.set_label_size(FONT_SIZE);
+    status_label.set_align(Align::Left | Align::Inside);
+
+    let mut remove_version_button = Button::new(
+        STORAGE_WIDTH - LEFT_MARGIN - BUTTON_X * 4 - BUTTON_SPACING * 3,
+        buttons_y,
+        BUTTON_X,
         CONTROL_HEIGHT,
-        "JVM Arguments (optional):",
+        "Delete Version",
     );
-    jvm_args_label.set_label_font(text_font);
-    jvm_args_label.set_label_size(FONT_SIZE);
-    jvm_args_label.set_align(Align::Left | Align::Inside);
+    remove_version_button.set_label_font(text_font);
+    remove_version_button.set_label_size(FONT_SIZE);
+    remove_version_button.set_frame(FrameType::UpBox);
+    remove_version_button.set_color(GRAY_COLOR);
 
-    let mut jvm_hint_label = Frame::new(
-        JVM_HINT_X,
-        JVM_ARGS_Y,
-        JVM_HINT_WIDTH,
+    let mut find_button = Button::new(
+        STORAGE_WIDTH - LEFT_MARGIN - BUTTON_X * 3 - BUTTON_SPACING * 2,
+        buttons_y,
+        BUTTON_X,
         CONTROL_HEIGHT,
-        "Example: -Xmx2G -Xms512M",
+        "Find Unused",
     );
-    jvm_hint_label.set_label_font(text_font);
-    jvm_hint_label.set_label_size(SMALL_FONT_SIZE);
-    jvm_hint_label.set_label_color(HINT_TEXT_COLOR);
-    jvm_hint_label.set_align(Align::Left | Align::Inside);
+    find_button.set_label_font(text_font);
+    find_button.set_label_size(FONT_SIZE);
+    find_button.set_frame(FrameType::UpBox);
+    find_button.set_color(GRAY_COLOR);
 
-    let mut jvm_args_input = Input::new(LEFT_MARGIN, JVM_INPUT_Y, INPUT_WIDTH, CONTROL_HEIGHT, "");
-    jvm_args_input.set_text_font(text_font);
-    jvm_args_input.set_text_size(FONT_SIZE);
-    if let Some(args) = &profile.jvm_args {
-        jvm_args_input.set_value(args);
-    }
+    let mut delete_button = Button::new(
+        STORAGE_WIDTH - LEFT_MARGIN - BUTTON_X * 2 - BUTTON_SPACING,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Delete Unused",
+    );
+    delete_button.set_label_font(text_font);
+    delete_button.set_label_size(FONT_SIZE);
+    delete_button.set_frame(FrameType::UpBox);
+    delete_button.set_color(GRAY_COLOR);
 
-    let mut save_button = Button::new(BUTTON_X, BUTTON_Y, BUTTON_X, CONTROL_HEIGHT, "Save");
-    save_button.set_label_font(text_font);
-    save_button.set_label_size(FONT_SIZE);
-    save_button.set_frame(FrameType::UpBox);
-    save_button.set_color(GRAY_COLOR);
+    let mut close_button = Button::new(
+        STORAGE_WIDTH - LEFT_MARGIN - BUTTON_X,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Close",
+    );
+    close_button.set_label_font(text_font);
+    close_button.set_label_size(FONT_SIZE);
+    close_button.set_frame(FrameType::UpBox);
+    close_button.set_color(GRAY_COLOR);
 
     setup_frame(win.width(), win.height());
     setup_title_bar(
-        "Edit Profile",
+        "Storage Manager",
         &mut load_image_from_data!("../themes/minecraft_icon.png").unwrap(),
         text_font,
         &win,
@@ -307,32 +2047,46 @@ pub fn edit_profile_dialog(text_font: Font, profile: &Profile) -> Option<Profile
 
     win.end();
 
-    let result = Arc::new(Mutex::new(None::<Profile>));
-    let result_clone = result.clone();
+    let run_action = Rc::new(run_action);
+    let version_rows: Rc<RefCell<Vec<Option<String>>>> = Rc::new(RefCell::new(Vec::new()));
+    let confirm_remove: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+
+    remove_version_button.set_callback({
+        let run_action = run_action.clone();
+        let version_rows = version_rows.clone();
+        let confirm_remove = confirm_remove.clone();
+        let usage_browser = usage_browser.clone();
+        move |_| {
+            let selected = usage_browser.value().max(1) as usize - 1;
+            let Some(version_id) = version_rows.borrow().get(selected).cloned().flatten() else {
+                return;
+            };
+            if confirm_remove.borrow().as_ref() == Some(&version_id) {
+                confirm_remove.borrow_mut().take();
+                run_action(StorageAction::RemoveVersion(version_id));
+            } else {
+                *confirm_remove.borrow_mut() = Some(version_id);
+            }
+        }
+    });
 
-    let username_clone = username_input.clone();
-    let jvm_args_clone = jvm_args_input.clone();
-    let mut win_clone = win.clone();
+    find_button.set_callback({
+        let run_action = run_action.clone();
+        move |_| run_action(StorageAction::FindUnused)
+    });
 
-    save_button.set_callback(move |_| {
-        let username = username_clone.value().trim().to_string();
-        let jvm_args = jvm_args_clone.value().trim().to_string();
+    delete_button.set_callback({
+        let run_action = run_action.clone();
+        move |_| run_action(StorageAction::DeleteUnused)
+    });
 
-        if !username.is_empty() {
-            let jvm_args = if jvm_args.is_empty() {
-                None
-            } else {
-                Some(jvm_args)
-            };
-            *result_clone.lock().unwrap() = Some(Profile { username, jvm_args });
-        }
+    let mut win_clone = win.clone();
+    close_button.set_callback(move |_| {
         win_clone.hide();
-        DIALOG_RUNNING.store(false, Ordering::SeqCst);
     });
 
     win.set_callback(move |w| {
         w.hide();
-        DIALOG_RUNNING.store(false, Ordering::SeqCst);
     });
 
     win.show();
@@ -341,12 +2095,95 @@ pub fn edit_profile_dialog(text_font: Font, profile: &Profile) -> Option<Profile
     #[cfg(target_os = "windows")]
     adjust_window(&win);
 
-    while win.shown() {
-        app::wait();
-    }
+    run_action(StorageAction::Refresh);
 
-    DIALOG_RUNNING.store(false, Ordering::SeqCst);
-    result.lock().unwrap().clone()
+    let mut rendered_revision = None;
+    app::add_timeout3(0.0, move |handle| {
+        if !win.shown() {
+            STORAGE_OPEN.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        let state = state.lock().unwrap();
+        if rendered_revision != Some(state.revision) {
+            let selected = usage_browser.value();
+            let mut rows = version_rows.borrow_mut();
+            usage_browser.clear();
+            rows.clear();
+            if let Some(usage) = &state.usage {
+                for (category, bytes) in &usage.categories {
+                    usage_browser.add(&format!("{}\t{}", category.name(), format_size(*bytes)));
+                    rows.push(None);
+                }
+                usage_browser.add(&format!("Total\t{}", format_size(usage.total())));
+                rows.push(None);
+                for (id, bytes) in &usage.versions {
+                    usage_browser.add(&format!("    {}\t{}", id, format_size(*bytes)));
+                    rows.push(Some(id.clone()));
+                }
+            }
+            if selected > 0 && selected as usize <= rows.len() {
+                usage_browser.select(selected);
+            }
+
+            unused_browser.clear();
+            for file in state.plan.iter().flat_map(|plan| &plan.files) {
+                unused_browser.add(&format!(
+                    "{}\t{}\t{}",
+                    file.category.name(),
+                    format_size(file.bytes),
+                    file.path.display()
+                ));
+            }
+            rendered_revision = Some(state.revision);
+        }
+
+        let selected_version = version_rows
+            .borrow()
+            .get(usage_browser.value().max(1) as usize - 1)
+            .cloned()
+            .flatten();
+        let confirming = confirm_remove
+            .borrow()
+            .as_ref()
+            .filter(|id| selected_version.as_ref() == Some(id))
+            .cloned();
+        if confirm_remove.borrow().is_some() && confirming.is_none() {
+            confirm_remove.borrow_mut().take();
+        }
+
+        let label = match &confirming {
+            Some(version_id) if !state.running => {
+                format!("Click Delete Version again to remove {}", version_id)
+            }
+            _ => state.message.clone(),
+        };
+        status_label.set_label(&label);
+
+        for (button, enabled) in [
+            (
+                &mut remove_version_button,
+                !state.running && selected_version.is_some(),
+            ),
+            (&mut find_button, !state.running),
+            (
+                &mut delete_button,
+                !state.running
+                    && state
+                        .plan
+                        .as_ref()
+                        .is_some_and(|plan| !plan.files.is_empty()),
+            ),
+        ] {
+            if enabled {
+                button.activate();
+            } else {
+                button.deactivate();
+            }
+        }
+
+        app::repeat_timeout3(DOWNLOADS_REFRESH_SECS, handle);
+    });
 }
 
 pub fn setup_font(app: app::App) -> Font {
@@ -407,25 +2244,152 @@ pub fn setup_font(app: app::App) -> Font {
     Font::Helvetica
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecoveryAction {
+    Retry,
+    ChooseJava,
+    Repair,
+}
+
+impl RecoveryAction {
+    fn label(&self) -> &'static str {
+        match self {
+            RecoveryAction::Retry => "Retry",
+            RecoveryAction::ChooseJava => "Choose Java",
+            RecoveryAction::Repair => "Repair",
+        }
+    }
+}
+
+type RecoveryHandler = Rc<dyn Fn(RecoveryAction)>;
+
+thread_local! {
+    static RECOVERY_HANDLER: RefCell<Option<RecoveryHandler>> = const { RefCell::new(None) };
+}
+
+pub fn set_recovery_handler(handler: impl Fn(RecoveryAction) + 'static) {
+    RECOVERY_HANDLER.with(|slot| *slot.borrow_mut() = Some(Rc::new(handler)));
+}
+
+pub struct ErrorReport {
+    pub message: String,
+    pub details: Option<String>,
+    pub crash: Option<CrashAnalysis>,
+    pub recovery: Option<RecoveryAction>,
+}
+
+impl From<String> for ErrorReport {
+    fn from(message: String) -> Self {
+        ErrorReport {
+            message,
+            details: None,
+            crash: None,
+            recovery: None,
+        }
+    }
+}
+
+impl From<LaunchError> for ErrorReport {
+    fn from(error: LaunchError) -> Self {
+        let message = launch_error_message(&error);
+        let recovery = recovery_action(&error);
+        let mut details = error.to_string();
+
+        match error {
+            LaunchError::ProcessExited {
+                log_tail, crash, ..
+            } => {
+                if !log_tail.is_empty() {
+                    details.push_str("\n\n");
+                    details.push_str(&log_tail.join("\n"));
+                }
+
+                ErrorReport {
+                    message,
+                    details: Some(details),
+                    crash: Some(*crash),
+                    recovery,
+                }
+            }
+            _ => ErrorReport {
+                message,
+                details: Some(details),
+                crash: None,
+                recovery,
+            },
+        }
+    }
+}
+
+fn recovery_action(error: &LaunchError) -> Option<RecoveryAction> {
+    match error {
+        LaunchError::JavaNotFound(_) => Some(RecoveryAction::ChooseJava),
+        LaunchError::Download(
+            DownloadError::Http(_)
+            | DownloadError::HttpStatus { .. }
+            | DownloadError::Incomplete { .. },
+        ) => Some(RecoveryAction::Retry),
+        LaunchError::Download(
+            DownloadError::ChecksumMismatch { .. } | DownloadError::SizeMismatch { .. },
+        ) => Some(RecoveryAction::Repair),
+        _ => None,
+    }
+}
+
+fn launch_error_message(error: &LaunchError) -> String {
+    match error {
+        LaunchError::JavaNotFound(path) => format!(
+            "Java was not found at {}. Install Java or press Choose Java to pick another installation.",
+            path.display()
+        ),
+        LaunchError::PermissionDenied(_) => {
+            "Permission denied while starting Java. Try running the launcher as administrator."
+                .to_string()
+        }
+        LaunchError::Spawn(e) => format!(
+            "Failed to start Java: {}. Try closing other applications and launching again.",
+            e
+        ),
+        LaunchError::ProcessExited { code, crash, .. } => {
+            match (crash.issues.first(), &crash.description) {
+                (Some(issue), _) => format!("Minecraft crashed: {}. {}", issue.title, issue.hint),
+                (None, Some(description)) => format!(
+                    "Minecraft crashed: {}. Press Details to see the crash analysis.",
+                    description
+                ),
+                (None, None) => format!(
+                    "Minecraft exited with code {}. This may be due to Java version incompatibility, insufficient system resources, or game file corruption.",
+                    code
+                ),
+            }
+        }
+        LaunchError::Download(DownloadError::Http(_)) => {
+            "Could not reach the download server. Check your internet connection and press Retry."
+                .to_string()
+        }
+        LaunchError::Download(DownloadError::HttpStatus { status, .. }) => format!(
+            "The download server answered with HTTP {}. Press Retry or try again later.",
+            status
+        ),
+        LaunchError::Download(
+            DownloadError::ChecksumMismatch { url, .. } | DownloadError::SizeMismatch { url, .. },
+        ) => format!(
+            "A downloaded file was corrupted ({}). Press Repair to verify and download broken files again.",
+            url
+        ),
+        LaunchError::AuthServer(_) => {
+            format!("{}. Check the auth server address or log in again.", error)
+        }
+        _ => error.to_string(),
+    }
+}
+
 pub fn show_error_dialog(message: &str, text_font: Font) {
-    let display_message = if message.contains("JVM allocation heap")
-        || message.contains("heap space")
-    {
-        "Not enough memory to start Minecraft. Try closing other programs or increasing the memory allocation in the profile settings."
-    } else if message.contains("java.lang.ClassNotFoundException") {
-        "Could not find the required Java class. The Java installation may be corrupted or incompatible."
-    } else if message.contains("java.io.IOException") {
-        "I/O error. Check if you have write permissions to the game directory and if there is enough space on the disk."
-    } else if message.contains("java.lang.OutOfMemoryError") {
-        "Not enough memory to start Minecraft. Try closing other programs or increasing the memory allocation."
-    } else if message.contains("exited immediately") || message.contains("crashed during startup") {
-        "Minecraft exited with an error when starting. This may be due to Java version incompatibility, insufficient system resources, or game file corruption."
-    } else if message.contains("no valid OpenGL") || message.contains("OpenGL Error") {
-        "OpenGL error. Update your video card drivers or make sure your computer supports the required OpenGL version."
-    } else {
-        message
-    };
+    show_error_report(&message.to_string().into(), text_font);
+}
 
+pub fn show_error_report(report: &ErrorReport, text_font: Font) {
+    let display_message = report.message.as_str();
     let error_width = DIALOG_WIDTH;
     let chars_per_line = ERROR_TEXT_MAX_WIDTH / CHARS_PER_LINE_DIVISOR;
 
@@ -479,28 +2443,65 @@ pub fn show_error_dialog(message: &str, text_font: Font) {
     text.set_label_font(text_font);
     text.set_label_size(FONT_SIZE);
 
-    let show_details = display_message != message;
+    let show_details = report.crash.is_some()
+        || report
+            .details
+            .as_ref()
+            .is_some_and(|details| details != display_message);
+    let recovery_handler = RECOVERY_HANDLER.with(|slot| slot.borrow().clone());
+    let recovery = report.recovery.filter(|_| recovery_handler.is_some());
     let buttons_y = window_height - BUTTONS_MARGIN;
-    let button_width = BUTTON_X;
+    let (button_width, recovery_x, ok_x, details_x) = match (recovery, show_details) {
+        (Some(_), true) => (
+            RECOVERY_BUTTON_WIDTH,
+            RECOVERY_BUTTON_X,
+            RECOVERY_BUTTON_X + RECOVERY_BUTTON_WIDTH + BUTTON_SPACING,
+            RECOVERY_BUTTON_X + (RECOVERY_BUTTON_WIDTH + BUTTON_SPACING) * 2,
+        ),
+        (Some(_), false) => (
+            BUTTON_X,
+            OK_BUTTON_X_WITH_DETAILS,
+            DETAILS_BUTTON_X,
+            DETAILS_BUTTON_X,
+        ),
+        (None, true) => (
+            BUTTON_X,
+            OK_BUTTON_X_SOLO,
+            OK_BUTTON_X_WITH_DETAILS,
+            DETAILS_BUTTON_X,
+        ),
+        (None, false) => (
+            BUTTON_X,
+            OK_BUTTON_X_SOLO,
+            OK_BUTTON_X_SOLO,
+            DETAILS_BUTTON_X,
+        ),
+    };
 
-    let mut ok_btn = Button::new(
-        if show_details {
-            OK_BUTTON_X_WITH_DETAILS
-        } else {
-            OK_BUTTON_X_SOLO
-        },
+    let mut recovery_btn = Button::new(
+        recovery_x,
         buttons_y,
         button_width,
         CONTROL_HEIGHT,
-        "OK",
+        recovery.map(|action| action.label()).unwrap_or_default(),
     );
+    if recovery.is_some() {
+        recovery_btn.set_label_font(text_font);
+        recovery_btn.set_label_size(FONT_SIZE);
+        recovery_btn.set_frame(FrameType::UpBox);
+        recovery_btn.set_color(GRAY_COLOR);
+    } else {
+        recovery_btn.hide();
+    }
+
+    let mut ok_btn = Button::new(ok_x, buttons_y, button_width, CONTROL_HEIGHT, "OK");
     ok_btn.set_label_font(text_font);
     ok_btn.set_label_size(FONT_SIZE);
     ok_btn.set_frame(FrameType::UpBox);
     ok_btn.set_color(GRAY_COLOR);
 
     let mut details_btn = Button::new(
-        DETAILS_BUTTON_X,
+        details_x,
         buttons_y,
         button_width,
         CONTROL_HEIGHT,
@@ -530,8 +2531,21 @@ pub fn show_error_dialog(message: &str, text_font: Font) {
         dialog_clone.hide();
     });
 
-    if show_details {
-        let original_message = message.to_string();
+    if let (Some(action), Some(handler)) = (recovery, recovery_handler) {
+        let mut dialog_clone = dialog.clone();
+        recovery_btn.set_callback(move |_| {
+            dialog_clone.hide();
+            handler(action);
+        });
+    }
+
+    if let Some(crash) = &report.crash {
+        let crash = crash.clone();
+        details_btn.set_callback(move |_| {
+            crash_details_dialog(text_font, &crash);
+        });
+    } else if show_details {
+        let original_message = report.details.clone().unwrap_or_default();
         let mut text_clone = text.clone();
         let mut dialog_clone = dialog.clone();
         details_btn.set_callback(move |_| {
@@ -552,6 +2566,140 @@ pub fn show_error_dialog(message: &str, text_font: Font) {
     adjust_window(&dialog);
 }
 
+pub fn crash_details_dialog(text_font: Font, crash: &CrashAnalysis) {
+    let mut win = Window::default()
+        .with_size(CRASH_DIALOG_WIDTH, CRASH_DIALOG_HEIGHT)
+        .with_label("Crash Details");
+    win.set_border(false);
+    win.make_modal(true);
+
+    let screen_width = app::screen_size().0 as i32;
+    let screen_height = app::screen_size().1 as i32;
+    win.set_pos(
+        (screen_width - CRASH_DIALOG_WIDTH) / CENTER_DIVISOR,
+        (screen_height - CRASH_DIALOG_HEIGHT) / CENTER_DIVISOR,
+    );
+
+    let mut bg = Frame::new(0, 0, CRASH_DIALOG_WIDTH, CRASH_DIALOG_HEIGHT, "");
+    bg.set_frame(FrameType::FlatBox);
+    bg.set_color(GRAY_COLOR);
+
+    let mut text = String::new();
+    match &crash.report_path {
+        Some(path) => text.push_str(&format!("Crash report: {}\n", path.display())),
+        None => text.push_str("No crash report was written.\n"),
+    }
+    if let Some(description) = &crash.description {
+        text.push_str(&format!("Description: {}\n", description));
+    }
+    if let Some(exception) = &crash.exception {
+        text.push_str(&format!("Exception: {}\n", exception));
+    }
+    if !crash.suspected_mods.is_empty() {
+        text.push_str("\nSuspected mods:\n");
+        for suspect in &crash.suspected_mods {
+            text.push_str(&format!("  - {}\n", suspect));
+        }
+    }
+    text.push_str("\nKnown issues:\n");
+    if crash.issues.is_empty() {
+        text.push_str("  No known issue matched this crash.\n");
+    }
+    for issue in &crash.issues {
+        text.push_str(&format!("  * {}: {}\n", issue.title, issue.hint));
+    }
+
+    let mut buffer = TextBuffer::default();
+    buffer.set_text(&text);
+    let mut display = TextDisplay::new(
+        LEFT_MARGIN,
+        TOP_MARGIN,
+        CRASH_DIALOG_WIDTH - LEFT_MARGIN * 2,
+        CRASH_DIALOG_HEIGHT - TOP_MARGIN - BUTTONS_MARGIN - BUTTON_SPACING,
+        "",
+    );
+    display.set_buffer(buffer);
+    display.set_frame(FrameType::DownBox);
+    display.set_text_font(text_font);
+    display.set_text_size(FONT_SIZE);
+    display.wrap_mode(fltk::text::WrapMode::AtBounds, 0);
+
+    let buttons_y = CRASH_DIALOG_HEIGHT - BUTTONS_MARGIN;
+
+    let mut open_button = Button::new(
+        CRASH_DIALOG_WIDTH - LEFT_MARGIN - BUTTON_X * 2 - BUTTON_SPACING,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "Open Report",
+    );
+    open_button.set_label_font(text_font);
+    open_button.set_label_size(FONT_SIZE);
+    open_button.set_frame(FrameType::UpBox);
+    open_button.set_color(GRAY_COLOR);
+
+    let mut ok_button = Button::new(
+        CRASH_DIALOG_WIDTH - LEFT_MARGIN - BUTTON_X,
+        buttons_y,
+        BUTTON_X,
+        CONTROL_HEIGHT,
+        "OK",
+    );
+    ok_button.set_label_font(text_font);
+    ok_button.set_label_size(FONT_SIZE);
+    ok_button.set_frame(FrameType::UpBox);
+    ok_button.set_color(GRAY_COLOR);
+
+    setup_frame(win.width(), win.height());
+    setup_title_bar(
+        "Crash Details",
+        &mut load_image_from_data!("../themes/windows98/error_icon.png").unwrap(),
+        text_font,
+        &win,
+    );
+
+    win.end();
+
+    match crash.report_path.clone() {
+        Some(report_path) => open_button.set_callback(move |_| {
+            #[cfg(target_os = "windows")]
+            {
+                let _ = Command::new("explorer").arg(&report_path).spawn();
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                let _ = Command::new("open").arg(&report_path).spawn();
+            }
+
+            #[cfg(all(unix, not(target_os = "macos")))]
+            {
+                let _ = Command::new("xdg-open").arg(&report_path).spawn();
+            }
+        }),
+        None => open_button.deactivate(),
+    }
+
+    let mut win_clone = win.clone();
+    ok_button.set_callback(move |_| {
+        win_clone.hide();
+    });
+
+    win.set_callback(move |w| {
+        w.hide();
+    });
+
+    win.show();
+    handle_drag(&mut win);
+
+    #[cfg(target_os = "windows")]
+    adjust_window(&win);
+
+    while win.shown() {
+        app::wait();
+    }
+}
+
 pub fn setup_frame(width: i32, height: i32) {
     let top_img = load_image_from_data!("../themes/windows98/top_frame.png").unwrap();
     let bottom_img = load_image_from_data!("../themes/windows98/bottom_frame.png").unwrap();
@@ -668,21 +2816,43 @@ pub fn setup_tiled_background() {
     });
 }
 
+pub struct MainControls {
+    pub version_choice: Choice,
+    pub profile_choice: Choice,
+    pub play_button: Button,
+    pub cancel_button: Button,
+    pub new_profile_button: Button,
+    pub edit_profile_button: Button,
+    pub java_choice: Choice,
+    pub status_label: Frame,
+    pub progress_bar: Frame,
+    pub tools_menu: MenuButton,
+    pub versions: VersionList,
+}
+
+#[derive(Clone)]
+pub struct VersionList {
+    versions: Rc<RefCell<Vec<(String, String)>>>,
+    refresh: Rc<dyn Fn()>,
+}
+
+impl VersionList {
+    pub fn add(&self, version_id: &str, version_type: &str) {
+        {
+            let mut versions = self.versions.borrow_mut();
+            if !versions.iter().any(|(id, _)| id == version_id) {
+                versions.insert(0, (version_id.to_string(), version_type.to_string()));
+            }
+        }
+        (self.refresh)();
+    }
+}
+
 pub fn setup_main_controls(
     text_font: Font,
-    versions: String,
+    versions: Vec<(String, String)>,
     profile_names: &Vec<String>,
-) -> (
-    Choice,
-    Choice,
-    Button,
-    Button,
-    Button,
-    Frame,
-    Choice,
-    Frame,
-    Frame,
-) {
+) -> MainControls {
     let column_width = (WIN_WIDTH - (PADDING * PADDING_MULTIPLIER)) / 2;
     let left_x = PADDING;
     let right_x = left_x + column_width + PADDING;
@@ -865,8 +3035,13 @@ pub fn setup_main_controls(
     welcome_frame.set_align(Align::Center | Align::Inside);
 
     let buttons_y = BOTTOM_SECTION_Y + BUTTONS_Y_OFFSET;
-    let play_button_x =
-        (WIN_WIDTH - BUTTON_WIDTH - FOLDER_BUTTON_WIDTH - BUTTON_SPACING) / CENTER_DIVISOR;
+    let play_button_x = (WIN_WIDTH
+        - BUTTON_WIDTH
+        - FOLDER_BUTTON_WIDTH
+        - CANCEL_BUTTON_WIDTH
+        - TOOLS_BUTTON_WIDTH
+        - BUTTON_SPACING * 3)
+        / CENTER_DIVISOR;
 
     let mut folder_button = Button::new(
         play_button_x,
@@ -930,6 +3105,33 @@ pub fn setup_main_controls(
     play.set_label_font(text_font);
     play.set_label_size(FONT_SIZE);
 
+    let mut cancel = Button::new(
+        play_button_x + FOLDER_BUTTON_WIDTH + BUTTON_WIDTH + BUTTON_SPACING * 2,
+        buttons_y,
+        CANCEL_BUTTON_WIDTH,
+        BUTTON_HEIGHT,
+        "Cancel",
+    );
+    cancel.set_label_font(text_font);
+    cancel.set_label_size(FONT_SIZE);
+    cancel.deactivate();
+
+    let mut tools_menu = MenuButton::new(
+        play_button_x
+            + FOLDER_BUTTON_WIDTH
+            + BUTTON_WIDTH
+            + CANCEL_BUTTON_WIDTH
+            + BUTTON_SPACING * 3,
+        buttons_y,
+        TOOLS_BUTTON_WIDTH,
+        BUTTON_HEIGHT,
+        "Tools",
+    );
+    tools_menu.set_label_font(text_font);
+    tools_menu.set_label_size(FONT_SIZE);
+    tools_menu.set_text_font(text_font);
+    tools_menu.set_text_size(FONT_SIZE);
+
     let progress_section_y = buttons_y + BUTTON_HEIGHT + PROGRESS_MARGIN;
 
     let mut progress_label = Frame::new(
@@ -978,22 +3180,11 @@ pub fn setup_main_controls(
         PROGRESS_HEIGHT,
     );
 
-    let mut update_dropdown = {
-        let versions_data: Vec<(String, String)> = versions
-            .split('|')
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<&str>>()
-            .chunks(2)
-            .filter_map(|chunk| {
-                if chunk.len() == 2 {
-                    Some((chunk[0].to_string(), chunk[1].to_string()))
-                } else {
-                    None
-                }
-            })
-            .collect();
+    let versions_data = Rc::new(RefCell::new(versions));
 
-        let mut choice = version_choice.clone();
+    let update_dropdown = {
+        let versions_data = versions_data.clone();
+        let choice = version_choice.clone();
         let checkboxes = checkboxes.clone();
 
         move || {
@@ -1008,6 +3199,7 @@ pub fn setup_main_controls(
                 Vec::new()
             } else {
                 versions_data
+                    .borrow()
                     .iter()
                     .filter(|(_, version_type)| {
                         let mapped_type = match version_type.as_str() {
@@ -1021,6 +3213,7 @@ pub fn setup_main_controls(
                     .collect::<Vec<String>>()
             };
 
+            let mut choice = choice.clone();
             choice.clear();
             let filtered_versions_str = filtered_versions.join("|");
             if !filtered_versions_str.is_empty() {
@@ -1031,7 +3224,7 @@ pub fn setup_main_controls(
     };
 
     for (i, _) in type_labels.iter().enumerate() {
-        let mut update = update_dropdown.clone();
+        let update = update_dropdown.clone();
         checkboxes[i].set_callback(move |_| {
             update();
         });
@@ -1055,17 +3248,22 @@ pub fn setup_main_controls(
 
     update_dropdown();
 
-    (
+    MainControls {
         version_choice,
         profile_choice,
-        play,
-        new_profile,
-        edit_profile,
-        welcome_frame,
+        play_button: play,
+        cancel_button: cancel,
+        new_profile_button: new_profile,
+        edit_profile_button: edit_profile,
         java_choice,
         status_label,
         progress_bar,
-    )
+        tools_menu,
+        versions: VersionList {
+            versions: versions_data,
+            refresh: Rc::new(update_dropdown),
+        },
+    }
 }
 
 pub fn setup_title_bar(title: &str, ico: &mut PngImage, font: Font, win: &Window) {
@@ -1213,4 +3411,4 @@ pub fn update_win98_progress_bar(progress_bar: &mut Frame, percentage: f64) {
 
     progress_bar.set_size(final_width, progress_bar.h());
     progress_bar.redraw();
-}
+}
\ No newline at end of file
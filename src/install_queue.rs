@@ -0,0 +1,274 @@
+use crate::{
+    cancel::CancellationToken, launcher::Launcher, models::LaunchProgress,
+    version_manager::ManifestService,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, mpsc},
+    thread,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InstallState {
+    Queued,
+    Running,
+    Paused,
+    Failed,
+    Cancelled,
+    Complete,
+}
+
+impl InstallState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InstallState::Queued => "Queued",
+            InstallState::Running => "Running",
+            InstallState::Paused => "Paused",
+            InstallState::Failed => "Failed",
+            InstallState::Cancelled => "Cancelled",
+            InstallState::Complete => "Complete",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            InstallState::Failed | InstallState::Cancelled | InstallState::Complete
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InstallEntry {
+    pub id: u64,
+    pub version_id: String,
+    pub state: InstallState,
+    pub progress: Option<LaunchProgress>,
+    pub error: Option<String>,
+}
+
+struct Slot {
+    entry: InstallEntry,
+    cancel: CancellationToken,
+    started: bool,
+}
+
+#[derive(Default)]
+struct QueueState {
+    slots: Vec<Slot>,
+    next_id: u64,
+    worker_active: bool,
+}
+
+pub struct InstallQueue {
+    game_dir: PathBuf,
+    cache_dir: PathBuf,
+    manifest: Arc<ManifestService>,
+    state: Mutex<QueueState>,
+}
+
+impl InstallQueue {
+    pub fn new(game_dir: &Path, cache_dir: &Path, manifest: Arc<ManifestService>) -> Arc<Self> {
+        Arc::new(InstallQueue {
+            game_dir: game_dir.to_path_buf(),
+            cache_dir: cache_dir.to_path_buf(),
+            manifest,
+            state: Mutex::new(QueueState::default()),
+        })
+    }
+
+    pub fn enqueue(self: &Arc<Self>, version_id: &str) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        if let Some(slot) = state
+            .slots
+            .iter()
+            .find(|slot| slot.entry.version_id == version_id && !slot.entry.state.is_finished())
+        {
+            return slot.entry.id;
+        }
+
+        state.next_id += 1;
+        let id = state.next_id;
+        state.slots.push(Slot {
+            entry: InstallEntry {
+                id,
+                version_id: version_id.to_string(),
+                state: InstallState::Queued,
+                progress: None,
+                error: None,
+            },
+            cancel: CancellationToken::new(),
+            started: false,
+        });
+        self.start_worker(&mut state);
+        id
+    }
+
+    pub fn entries(&self) -> Vec<InstallEntry> {
+        self.state
+            .lock()
+            .unwrap()
+            .slots
+            .iter()
+            .map(|slot| slot.entry.clone())
+            .collect()
+    }
+
+    pub fn pause(&self, id: u64) -> bool {
+        self.update(id, |slot| match slot.entry.state {
+            InstallState::Queued => {
+                slot.entry.state = InstallState::Paused;
+                true
+            }
+            InstallState::Running => {
+                slot.cancel.cancel();
+                slot.entry.state = InstallState::Paused;
+                true
+            }
+            _ => false,
+        })
+    }
+
+    pub fn resume(self: &Arc<Self>, id: u64) -> bool {
+        let resumed = self.update(id, |slot| {
+            if slot.entry.state != InstallState::Paused {
+                return false;
+            }
+            if slot.cancel.is_cancelled() {
+                slot.cancel = CancellationToken::new();
+            }
+            slot.entry.state = InstallState::Queued;
+            true
+        });
+
+        if resumed {
+            self.start_worker(&mut self.state.lock().unwrap());
+        }
+        resumed
+    }
+
+    pub fn retry(self: &Arc<Self>, id: u64) -> bool {
+        let retried = self.update(id, |slot| {
+            if !matches!(
+                slot.entry.state,
+                InstallState::Failed | InstallState::Cancelled
+            ) {
+                return false;
+            }
+            slot.cancel = CancellationToken::new();
+            slot.entry.state = InstallState::Queued;
+            slot.entry.progress = None;
+            slot.entry.error = None;
+            true
+        });
+
+        if retried {
+            self.start_worker(&mut self.state.lock().unwrap());
+        }
+        retried
+    }
+
+    pub fn cancel(&self, id: u64) -> bool {
+        self.update(id, |slot| {
+            if slot.entry.state.is_finished() {
+                return false;
+            }
+            slot.cancel.cancel();
+            if !slot.started || slot.entry.state != InstallState::Running {
+                slot.entry.state = InstallState::Cancelled;
+            }
+            true
+        })
+    }
+
+    fn update(&self, id: u64, f: impl FnOnce(&mut Slot) -> bool) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.slots.iter_mut().find(|slot| slot.entry.id == id) {
+            Some(slot) => f(slot),
+            None => false,
+        }
+    }
+
+    fn start_worker(self: &Arc<Self>, state: &mut QueueState) {
+        if state.worker_active {
+            return;
+        }
+        state.worker_active = true;
+
+        let queue = self.clone();
+        thread::spawn(move || {
+            while let Some((id, version_id, cancel)) = queue.next_queued() {
+                let result = queue.install(id, &version_id, &cancel);
+                queue.update(id, |slot| {
+                    slot.started = false;
+                    slot.entry.state = match (result, slot.entry.state) {
+                        (Ok(()), _) => InstallState::Complete,
+                        (Err(_), InstallState::Running) if cancel.is_cancelled() => {
+                            InstallState::Cancelled
+                        }
+                        (Err(e), InstallState::Running) => {
+                            slot.entry.error = Some(e);
+                            InstallState::Failed
+                        }
+                        (Err(_), state) => state,
+                    };
+                    true
+                });
+            }
+        });
+    }
+
+    fn next_queued(&self) -> Option<(u64, String, CancellationToken)> {
+        let mut state = self.state.lock().unwrap();
+        let Some(slot) = state
+            .slots
+            .iter_mut()
+            .find(|slot| slot.entry.state == InstallState::Queued && !slot.started)
+        else {
+            state.worker_active = false;
+            return None;
+        };
+
+        slot.started = true;
+        slot.entry.state = InstallState::Running;
+        Some((
+            slot.entry.id,
+            slot.entry.version_id.clone(),
+            slot.cancel.clone(),
+        ))
+    }
+
+    fn install(
+        self: &Arc<Self>,
+        id: u64,
+        version_id: &str,
+        cancel: &CancellationToken,
+    ) -> Result<(), String> {
+        let (sender, receiver) = mpsc::channel::<LaunchProgress>();
+        let queue = self.clone();
+        let progress_thread = thread::spawn(move || {
+            for progress in receiver {
+                queue.update(id, |slot| {
+                    slot.entry.progress = Some(progress);
+                    true
+                });
+            }
+        });
+
+        let launcher = Launcher::builder(&self.game_dir)
+            .cache_dir(&self.cache_dir)
+            .manifest(self.manifest.clone())
+            .progress(sender)
+            .cancel(cancel.clone())
+            .build();
+
+        let result = match launcher.resolve_version(version_id) {
+            Ok(version_data) => launcher.install(&version_data).map_err(|e| e.to_string()),
+            Err(e) => Err(format!("Failed to load version {}: {}", version_id, e)),
+        };
+
+        drop(launcher);
+        let _ = progress_thread.join();
+        result
+    }
+}
\ No newline at end of file
@@ -0,0 +1,251 @@
+use crate::{
+    cancel::CancellationToken,
+    download_engine::{DownloadJob, part_path},
+    downloader::{
+        asset_index_job, asset_object_jobs, client_jar_job, is_file_valid, is_native_excluded,
+        library_jobs, logging_config_job, native_jobs,
+    },
+    error::DownloadError,
+    models::{AssetIndexData, Extract, VersionData},
+};
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+const PROGRESS_STEP: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IssueKind {
+    Missing,
+    Corrupt,
+    Extra,
+}
+
+impl IssueKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IssueKind::Missing => "Missing",
+            IssueKind::Corrupt => "Corrupt",
+            IssueKind::Extra => "Extra",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct NativeExtract {
+    pub jar: PathBuf,
+    pub natives_dir: PathBuf,
+    pub extract: Option<Extract>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FileIssue {
+    pub kind: IssueKind,
+    pub path: PathBuf,
+    pub(crate) download: Option<DownloadJob>,
+    pub(crate) extract: Option<NativeExtract>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct VerifyReport {
+    pub checked: usize,
+    pub issues: Vec<FileIssue>,
+}
+
+impl VerifyReport {
+    pub fn count(&self, kind: IssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+
+    pub fn broken(&self) -> impl Iterator<Item = &FileIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind != IssueKind::Extra)
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.broken().next().is_none()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} files checked: {} missing, {} corrupt, {} extra",
+            self.checked,
+            self.count(IssueKind::Missing),
+            self.count(IssueKind::Corrupt),
+            self.count(IssueKind::Extra)
+        )
+    }
+}
+
+pub fn natives_dir(game_dir: &Path, version_data: &VersionData) -> PathBuf {
+    game_dir
+        .join("versions")
+        .join(&version_data.id)
+        .join("natives")
+}
+
+pub fn verify_version(
+    game_dir: &Path,
+    libraries_dir: &Path,
+    version_data: &VersionData,
+    cancel: &CancellationToken,
+    on_progress: impl Fn(usize, usize, &str),
+) -> Result<VerifyReport, DownloadError> {
+    let natives_dir = natives_dir(game_dir, version_data);
+    let natives = native_jobs(version_data, &natives_dir);
+
+    let mut jobs: Vec<DownloadJob> = client_jar_job(game_dir, version_data)
+        .into_iter()
+        .chain(library_jobs(&version_data.libraries, libraries_dir))
+        .chain(natives.iter().map(|(job, _)| job.clone()))
+        .chain(logging_config_job(game_dir, version_data))
+        .collect();
+
+    let index_job = asset_index_job(game_dir, version_data)?;
+    let asset_objects = if is_file_valid(&index_job.dest, index_job.sha1.as_deref(), index_job.size)
+    {
+        let asset_index_data: AssetIndexData =
+            serde_json::from_str(&fs::read_to_string(&index_job.dest)?)?;
+        asset_object_jobs(&asset_index_data, &game_dir.join("assets").join("objects"))
+    } else {
+        Vec::new()
+    };
+    jobs.push(index_job);
+    jobs.extend(asset_objects);
+
+    let mut report = VerifyReport::default();
+    for (i, job) in jobs.iter().enumerate() {
+        cancel.check()?;
+        if i % PROGRESS_STEP == 0 {
+            on_progress(i, jobs.len(), &job.label);
+        }
+
+        let kind = if !job.dest.is_file() {
+            Some(IssueKind::Missing)
+        } else if !is_file_valid(&job.dest, job.sha1.as_deref(), job.size) {
+            Some(IssueKind::Corrupt)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            report.issues.push(FileIssue {
+                kind,
+                path: job.dest.clone(),
+                download: Some(job.clone()),
+                extract: natives
+                    .iter()
+                    .find(|(native, _)| native.dest == job.dest)
+                    .map(|(native, extract)| NativeExtract {
+                        jar: native.dest.clone(),
+                        natives_dir: natives_dir.clone(),
+                        extract: (*extract).clone(),
+                    }),
+            });
+        }
+
+        let part = part_path(&job.dest);
+        if part.exists() {
+            report.issues.push(FileIssue {
+                kind: IssueKind::Extra,
+                path: part,
+                download: None,
+                extract: None,
+            });
+        }
+    }
+    report.checked = jobs.len();
+
+    verify_natives(&natives_dir, &natives, &mut report, cancel)?;
+    on_progress(jobs.len(), jobs.len(), "");
+
+    Ok(report)
+}
+
+fn verify_natives(
+    natives_dir: &Path,
+    natives: &[(DownloadJob, &Option<Extract>)],
+    report: &mut VerifyReport,
+    cancel: &CancellationToken,
+) -> Result<(), DownloadError> {
+    let mut expected: HashSet<PathBuf> = HashSet::new();
+    let mut scan_extras = true;
+
+    for (job, extract) in natives {
+        expected.insert(job.dest.clone());
+        expected.insert(part_path(&job.dest));
+        if report.issues.iter().any(|issue| issue.path == job.dest) {
+            scan_extras = false;
+            continue;
+        }
+
+        let mut archive = ZipArchive::new(File::open(&job.dest)?)?;
+        for i in 0..archive.len() {
+            cancel.check()?;
+            let file = archive.by_index(i)?;
+            if file.is_dir() || is_native_excluded(file.name(), extract) {
+                continue;
+            }
+
+            let path = natives_dir.join(file.name());
+            report.checked += 1;
+            let kind = match fs::metadata(&path) {
+                Ok(metadata) if metadata.len() == file.size() => None,
+                Ok(_) => Some(IssueKind::Corrupt),
+                Err(_) => Some(IssueKind::Missing),
+            };
+            if let Some(kind) = kind {
+                report.issues.push(FileIssue {
+                    kind,
+                    path: path.clone(),
+                    download: None,
+                    extract: Some(NativeExtract {
+                        jar: job.dest.clone(),
+                        natives_dir: natives_dir.to_path_buf(),
+                        extract: (*extract).clone(),
+                    }),
+                });
+            }
+            expected.insert(path);
+        }
+    }
+
+    if scan_extras {
+        let mut files = Vec::new();
+        collect_files(natives_dir, &mut files);
+        report.issues.extend(
+            files
+                .into_iter()
+                .filter(|path| !expected.contains(path))
+                .map(|path| FileIssue {
+                    kind: IssueKind::Extra,
+                    path,
+                    download: None,
+                    extract: None,
+                }),
+        );
+    }
+
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+    {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
\ No newline at end of file
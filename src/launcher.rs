@@ -1,331 +1,771 @@
 use crate::{
+    arguments::build_arguments,
+    background_jobs::BackgroundJobs,
+    cancel::CancellationToken,
+    crash_analyzer,
+    download_engine::{DownloadEngine, JobProgress},
     downloader,
-    models::{LaunchProgress, LaunchStage, VersionData},
+    error::{DownloadError, LaunchError},
+    game_log::{GameOutput, LOGS_DIR, LogLine},
+    integrity::{self, VerifyReport},
+    models::{LaunchProgress, LaunchSession, LaunchStage, VersionData},
+    process_registry::GameProcess,
+    version_manager::{ManifestService, resolve_version},
+    yggdrasil,
 };
-use std::{fs, io, path::PathBuf, process::Command, sync::mpsc, thread, time::Duration};
-
-pub fn launch_minecraft(
-    version_id: &str,
-    username: &str,
-    version_data: &VersionData,
-    java_path: Option<PathBuf>,
-    jvm_args: Option<Vec<String>>,
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{Arc, mpsc},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const LAUNCHER_NAME: &str = "minecraft-launcher";
+const REPAIR_PASSES: usize = 2;
+const CLASSPATH_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+
+pub struct Launcher {
+    game_dir: PathBuf,
+    cache_dir: PathBuf,
+    engine: DownloadEngine,
+    manifest: Arc<ManifestService>,
     progress: Option<mpsc::Sender<LaunchProgress>>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let send_progress = |stage: LaunchStage, current: usize, total: usize, message: String| {
-        if let Some(progress_sender) = &progress {
+    log: Option<mpsc::Sender<LogLine>>,
+    cancel: CancellationToken,
+    background_jobs: Option<Arc<BackgroundJobs>>,
+}
+
+pub struct LauncherBuilder {
+    game_dir: PathBuf,
+    cache_dir: Option<PathBuf>,
+    client: Option<reqwest::blocking::Client>,
+    concurrency: Option<usize>,
+    manifest: Option<Arc<ManifestService>>,
+    progress: Option<mpsc::Sender<LaunchProgress>>,
+    log: Option<mpsc::Sender<LogLine>>,
+    cancel: CancellationToken,
+    background_jobs: Option<Arc<BackgroundJobs>>,
+}
+
+impl LauncherBuilder {
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    pub fn client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    pub fn manifest(mut self, manifest: Arc<ManifestService>) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    pub fn progress(mut self, progress: mpsc::Sender<LaunchProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub fn log(mut self, log: mpsc::Sender<LogLine>) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    pub fn cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn background_jobs(mut self, background_jobs: Arc<BackgroundJobs>) -> Self {
+        self.background_jobs = Some(background_jobs);
+        self
+    }
+
+    pub fn build(self) -> Launcher {
+        let engine = match self.client {
+            Some(client) => DownloadEngine::new(client),
+            None => DownloadEngine::shared().clone(),
+        };
+        let engine = match self.concurrency {
+            Some(concurrency) => engine.with_concurrency(concurrency),
+            None => engine,
+        };
+        let manifest = self.manifest.unwrap_or_else(|| {
+            Arc::new(ManifestService::new(&self.game_dir).with_client(engine.client().clone()))
+        });
+
+        Launcher {
+            cache_dir: self
+                .cache_dir
+                .unwrap_or_else(|| self.game_dir.join("libraries")),
+            game_dir: self.game_dir,
+            engine,
+            manifest,
+            progress: self.progress,
+            log: self.log,
+            cancel: self.cancel,
+            background_jobs: self.background_jobs,
+        }
+    }
+}
+
+impl Launcher {
+    pub fn builder(game_dir: impl Into<PathBuf>) -> LauncherBuilder {
+        LauncherBuilder {
+            game_dir: game_dir.into(),
+            cache_dir: None,
+            client: None,
+            concurrency: None,
+            manifest: None,
+            progress: None,
+            log: None,
+            cancel: CancellationToken::new(),
+            background_jobs: None,
+        }
+    }
+
+    pub fn game_dir(&self) -> &Path {
+        &self.game_dir
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    pub fn engine(&self) -> &DownloadEngine {
+        &self.engine
+    }
+
+    pub fn manifest(&self) -> &ManifestService {
+        &self.manifest
+    }
+
+    pub fn resolve_version(
+        &self,
+        version_id: &str,
+    ) -> Result<VersionData, Box<dyn std::error::Error>> {
+        resolve_version(&self.game_dir, &self.manifest, version_id)
+    }
+
+    pub fn install(&self, version_data: &VersionData) -> Result<(), LaunchError> {
+        let result = self.download_version(version_data);
+
+        if let Err(e) = &result {
+            self.report(e.to_string());
+        }
+
+        result
+    }
+
+    fn download_version(&self, version_data: &VersionData) -> Result<(), LaunchError> {
+        let send_progress = |stage: LaunchStage, current: usize, total: usize, message: String| {
+            if let Some(progress_sender) = &self.progress {
+                let _ = progress_sender.send(LaunchProgress {
+                    stage,
+                    current,
+                    total,
+                    message,
+                    bytes: None,
+                });
+            }
+        };
+
+        let game_dir = &self.game_dir;
+        let cache_dir = &self.cache_dir;
+
+        send_progress(
+            LaunchStage::PreparingLibraries,
+            0,
+            1,
+            format!("Downloading client {}...", version_data.jar_id()),
+        );
+        let client_progress = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::PreparingLibraries,
+                progress,
+                format!("Downloading client {}...", version_data.jar_id()),
+            );
+        };
+        downloader::download_client_jar(
+            &self.engine,
+            game_dir,
+            version_data,
+            Some(&client_progress),
+            &self.cancel,
+        )?;
+
+        let progress_callback = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::DownloadingLibraries,
+                progress,
+                format!(
+                    "Downloading libraries ({}/{}): {}",
+                    progress.completed, progress.total, progress.job.label
+                ),
+            );
+        };
+        downloader::download_libraries(
+            &self.engine,
+            &version_data.libraries,
+            cache_dir,
+            Some(&progress_callback),
+            &self.cancel,
+        )?;
+
+        send_progress(
+            LaunchStage::ExtractingNatives,
+            0,
+            1,
+            "Extracting native libraries...".to_string(),
+        );
+        let natives_dir = integrity::natives_dir(game_dir, version_data);
+        fs::create_dir_all(&natives_dir)?;
+        downloader::download_and_extract_natives(
+            &self.engine,
+            version_data,
+            &natives_dir,
+            &self.cancel,
+        )?;
+        downloader::download_logging_config(&self.engine, game_dir, version_data, &self.cancel)?;
+
+        let progress_fn = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::DownloadingAssets,
+                progress,
+                format!(
+                    "Downloading assets ({}/{}): {}",
+                    progress.completed, progress.total, progress.job.label
+                ),
+            );
+        };
+        let remaining_assets = downloader::download_and_extract_assets(
+            &self.engine,
+            version_data,
+            game_dir,
+            Some(&progress_fn),
+            &self.cancel,
+        )?;
+
+        if let Some(remaining_assets) = remaining_assets {
+            send_progress(
+                LaunchStage::DownloadingAssets,
+                0,
+                1,
+                "Downloading remaining assets...".to_string(),
+            );
+            remaining_assets.wait();
+            self.cancel.check()?;
+
+            let status = remaining_assets.status();
+            if !status.failures.is_empty() {
+                return Err(DownloadError::Incomplete {
+                    failed: status.failures.len(),
+                    total: status.total,
+                }
+                .into());
+            }
+        }
+
+        send_progress(
+            LaunchStage::Complete,
+            1,
+            1,
+            format!("Installed {}", version_data.id),
+        );
+
+        Ok(())
+    }
+
+    pub fn verify(&self, version_data: &VersionData) -> Result<VerifyReport, LaunchError> {
+        let report = integrity::verify_version(
+            &self.game_dir,
+            &self.cache_dir,
+            version_data,
+            &self.cancel,
+            |current, total, label| {
+                if let Some(progress_sender) = &self.progress {
+                    let _ = progress_sender.send(LaunchProgress {
+                        stage: LaunchStage::Verifying,
+                        current,
+                        total,
+                        message: format!("Verifying ({}/{}): {}", current, total, label),
+                        bytes: None,
+                    });
+                }
+            },
+        )?;
+
+        if let Some(progress_sender) = &self.progress {
             let _ = progress_sender.send(LaunchProgress {
-                stage,
-                current,
-                total,
-                message,
+                stage: LaunchStage::Complete,
+                current: 1,
+                total: 1,
+                message: report.summary(),
+                bytes: None,
             });
-            thread::sleep(Duration::from_millis(15));
         }
-    };
 
-    send_progress(
-        LaunchStage::PreparingLibraries,
-        0,
-        100,
-        "Preparing environment...".to_string(),
-    );
+        Ok(report)
+    }
 
-    let game_dir = crate::get_game_directory();
-    let version_dir = game_dir.join("versions").join(version_id);
+    pub fn repair(
+        &self,
+        version_data: &VersionData,
+        report: &VerifyReport,
+    ) -> Result<VerifyReport, LaunchError> {
+        let progress_callback = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::DownloadingLibraries,
+                progress,
+                format!(
+                    "Repairing ({}/{}): {}",
+                    progress.completed, progress.total, progress.job.label
+                ),
+            );
+        };
 
-    let client_jar = version_dir.join("client.jar");
+        let mut report = report.clone();
+        for _ in 0..REPAIR_PASSES {
+            if report.is_ok() {
+                break;
+            }
+            match downloader::repair_files(
+                &self.engine,
+                &report,
+                Some(&progress_callback),
+                &self.cancel,
+            ) {
+                Ok(()) | Err(DownloadError::Incomplete { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+            report = self.verify(version_data)?;
+        }
 
-    let natives_dir = version_dir.join("natives");
+        Ok(report)
+    }
 
-    if !natives_dir.exists() {
-        fs::create_dir_all(&natives_dir)?;
+    pub fn launch(
+        &self,
+        version_data: &VersionData,
+        session: &LaunchSession,
+        java_path: Option<PathBuf>,
+        jvm_args: Option<Vec<String>>,
+    ) -> Result<GameProcess, LaunchError> {
+        let result = self.start(version_data, session, java_path, jvm_args);
+
+        if let Err(e) = &result {
+            self.report(e.to_string());
+        }
+
+        result
     }
 
-    send_progress(
-        LaunchStage::PreparingLibraries,
-        10,
-        100,
-        "Reading version information...".to_string(),
-    );
-
-    let libraries = &version_data.libraries;
-    let lib_total = libraries.len();
-
-    send_progress(
-        LaunchStage::DownloadingLibraries,
-        20,
-        100,
-        format!("Preparing libraries (0/{})...", lib_total),
-    );
-
-    let cache_dir = dirs::config_dir()
-        .unwrap()
-        .join("minecraft_launcher")
-        .join("cache");
-
-    if !cache_dir.exists() {
-        fs::create_dir_all(&cache_dir)?;
+    pub fn report(&self, message: String) {
+        if let Some(progress_sender) = &self.progress {
+            let _ = progress_sender.send(LaunchProgress {
+                stage: LaunchStage::Complete,
+                current: 1,
+                total: 1,
+                message,
+                bytes: None,
+            });
+        }
     }
 
-    let mut progress_index = 0;
-    let mut progress_callback = |i: usize, total: usize, name: &str| {
-        progress_index = i;
+    fn start(
+        &self,
+        version_data: &VersionData,
+        session: &LaunchSession,
+        java_path: Option<PathBuf>,
+        jvm_args: Option<Vec<String>>,
+    ) -> Result<GameProcess, LaunchError> {
+        let send_progress = |stage: LaunchStage, current: usize, total: usize, message: String| {
+            if let Some(progress_sender) = &self.progress {
+                let _ = progress_sender.send(LaunchProgress {
+                    stage,
+                    current,
+                    total,
+                    message,
+                    bytes: None,
+                });
+                thread::sleep(Duration::from_millis(15));
+            }
+        };
+
+        send_progress(
+            LaunchStage::PreparingLibraries,
+            0,
+            100,
+            "Preparing environment...".to_string(),
+        );
+
+        let version_id = &version_data.id;
+        let game_dir = self.game_dir.clone();
+        let version_dir = game_dir.join("versions").join(version_id);
+
+        let client_jar = game_dir
+            .join("versions")
+            .join(version_data.jar_id())
+            .join("client.jar");
+
+        let natives_dir = version_dir.join("natives");
+
+        if !natives_dir.exists() {
+            fs::create_dir_all(&natives_dir)?;
+        }
+
+        send_progress(
+            LaunchStage::PreparingLibraries,
+            10,
+            100,
+            "Reading version information...".to_string(),
+        );
+
+        let libraries = &version_data.libraries;
+        let lib_total = libraries.len();
+
         send_progress(
             LaunchStage::DownloadingLibraries,
-            20 + (i * 20 / total),
+            20,
             100,
-            format!("Preparing libraries ({}/{}): {}", i + 1, total, name),
+            format!("Preparing libraries (0/{})...", lib_total),
         );
-    };
-
-    let classpath_paths = downloader::download_libraries(
-        version_data,
-        &cache_dir,
-        Some(&mut progress_callback as &mut dyn FnMut(usize, usize, &str)),
-    )?;
-
-    let mut classpath = String::new();
-    for (i, path) in classpath_paths.iter().enumerate() {
-        if i > 0 {
-            classpath.push_str(if cfg!(windows) { ";" } else { ":" });
+
+        let cache_dir = &self.cache_dir;
+
+        if !cache_dir.exists() {
+            fs::create_dir_all(cache_dir)?;
         }
-        classpath.push_str(&path.to_string_lossy());
-    }
 
-    downloader::download_and_extract_natives(version_data, &natives_dir)?;
+        let client_progress = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::DownloadingLibraries,
+                progress,
+                format!("Downloading client {}...", version_data.jar_id()),
+            );
+        };
+        downloader::download_client_jar(
+            &self.engine,
+            &game_dir,
+            version_data,
+            Some(&client_progress),
+            &self.cancel,
+        )?;
+
+        let progress_callback = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::DownloadingLibraries,
+                progress,
+                format!(
+                    "Preparing libraries ({}/{}): {}",
+                    progress.completed, progress.total, progress.job.label
+                ),
+            );
+        };
+
+        let classpath_paths = downloader::download_libraries(
+            &self.engine,
+            &version_data.libraries,
+            cache_dir,
+            Some(&progress_callback),
+            &self.cancel,
+        )?;
+
+        let mut classpath = String::new();
+        for (i, path) in classpath_paths.iter().enumerate() {
+            if i > 0 {
+                classpath.push_str(CLASSPATH_SEPARATOR);
+            }
+            classpath.push_str(&path.to_string_lossy());
+        }
 
-    send_progress(
-        LaunchStage::ExtractingNatives,
-        50,
-        100,
-        "Native libraries extracted".to_string(),
-    );
+        downloader::download_and_extract_natives(
+            &self.engine,
+            version_data,
+            &natives_dir,
+            &self.cancel,
+        )?;
 
-    send_progress(
-        LaunchStage::PreparingAssets,
-        50,
-        100,
-        "Preparing game assets...".to_string(),
-    );
+        send_progress(
+            LaunchStage::ExtractingNatives,
+            50,
+            100,
+            "Native libraries extracted".to_string(),
+        );
 
-    let game_assets_dir = game_dir.clone();
+        send_progress(
+            LaunchStage::PreparingAssets,
+            50,
+            100,
+            "Preparing game assets...".to_string(),
+        );
+
+        let game_assets_dir = game_dir.clone();
+
+        let progress_fn = |progress: &JobProgress| {
+            self.send_download_progress(
+                LaunchStage::DownloadingAssets,
+                progress,
+                format!("Downloading assets: Required: {}", progress.job.label),
+            );
+        };
+
+        let background_download = downloader::download_and_extract_assets(
+            &self.engine,
+            version_data,
+            &game_assets_dir,
+            Some(&progress_fn),
+            &self.cancel,
+        )?;
+        if let (Some(job), Some(background_jobs)) = (&background_download, &self.background_jobs) {
+            background_jobs.add(job.clone());
+        }
 
-    let progress_fn = move |current: usize, total: usize, message: &str| {
-        let percentage = 60 + (current * 15 / total);
         send_progress(
-            LaunchStage::DownloadingAssets,
-            percentage,
+            LaunchStage::AssetLoadComplete,
+            75,
             100,
-            format!("Downloading assets: {}", message),
+            "Required assets downloaded. Launching game...".to_string(),
         );
-    };
-
-    downloader::download_and_extract_assets(version_data, &game_assets_dir, Some(progress_fn))?;
-
-    send_progress(
-        LaunchStage::AssetLoadComplete,
-        75,
-        100,
-        "Essential assets ready".to_string(),
-    );
-
-    send_progress(
-        LaunchStage::ValidatingJava,
-        80,
-        100,
-        "Validating Java installation...".to_string(),
-    );
-
-    if !classpath.is_empty() {
-        classpath.push_str(if cfg!(windows) { ";" } else { ":" });
-    }
-    classpath.push_str(&client_jar.to_string_lossy());
 
-    send_progress(
-        LaunchStage::BuildingArguments,
-        85,
-        100,
-        "Building game arguments...".to_string(),
-    );
+        send_progress(
+            LaunchStage::ValidatingJava,
+            80,
+            100,
+            "Validating Java installation...".to_string(),
+        );
+
+        if !classpath.is_empty() {
+            classpath.push_str(CLASSPATH_SEPARATOR);
+        }
+        classpath.push_str(&client_jar.to_string_lossy());
+
+        send_progress(
+            LaunchStage::BuildingArguments,
+            85,
+            100,
+            "Building game arguments...".to_string(),
+        );
+
+        let assets_dir = game_assets_dir.join("assets");
+        let asset_index_id = version_data
+            .asset_index
+            .as_ref()
+            .map(|index| index.id.clone())
+            .unwrap_or_default();
+        let game_assets = if asset_index_id == "legacy" || asset_index_id == "pre-1.6" {
+            assets_dir.join("legacy")
+        } else {
+            assets_dir.clone()
+        };
+
+        let mut placeholders: HashMap<&str, String> = HashMap::new();
+        placeholders.insert("auth_player_name", session.username.clone());
+        placeholders.insert("version_name", version_id.to_string());
+        placeholders.insert(
+            "game_directory",
+            game_assets_dir.to_string_lossy().to_string(),
+        );
+        placeholders.insert("assets_root", assets_dir.to_string_lossy().to_string());
+        placeholders.insert("game_assets", game_assets.to_string_lossy().to_string());
+        placeholders.insert("assets_index_name", asset_index_id);
+        placeholders.insert("auth_uuid", session.uuid.clone());
+        placeholders.insert("auth_access_token", session.access_token.clone());
+        placeholders.insert("auth_session", session.access_token.clone());
+        placeholders.insert(
+            "auth_xuid",
+            session.xuid.clone().unwrap_or_else(|| "0".to_string()),
+        );
+        placeholders.insert("clientid", "0".to_string());
+        placeholders.insert("user_type", session.user_type.clone());
+        placeholders.insert("user_properties", "{}".to_string());
+        placeholders.insert(
+            "version_type",
+            version_data
+                .version_type
+                .clone()
+                .unwrap_or_else(|| "release".to_string()),
+        );
+        placeholders.insert(
+            "natives_directory",
+            natives_dir.to_string_lossy().to_string(),
+        );
+        placeholders.insert("library_directory", cache_dir.to_string_lossy().to_string());
+        placeholders.insert("classpath", classpath);
+        placeholders.insert("classpath_separator", CLASSPATH_SEPARATOR.to_string());
+        placeholders.insert("launcher_name", LAUNCHER_NAME.to_string());
+        placeholders.insert("launcher_version", env!("CARGO_PKG_VERSION").to_string());
+
+        let arguments = build_arguments(version_data, &placeholders, &[]);
+
+        let java_executable = java_path.unwrap_or_else(|| "java".into());
+        let mut command = Command::new(&java_executable);
+        command.current_dir(&game_dir);
 
-    let java_executable = java_path.unwrap_or_else(|| "java".into());
-    let mut command = Command::new(java_executable);
+        if let Some(args) = jvm_args {
+            for arg in args {
+                command.arg(arg);
+            }
+        }
 
-    if let Some(args) = jvm_args {
-        for arg in args {
-            command.arg(arg);
+        if let Some(server_url) = &session.auth_server {
+            let injector_arguments = yggdrasil::injector_arguments(server_url, cache_dir)
+                .map_err(|e| LaunchError::AuthServer(e.to_string()))?;
+            command.args(injector_arguments);
         }
-    }
 
-    command.arg("-Djava.library.path=".to_string() + &natives_dir.to_string_lossy());
-    command.arg("-cp");
-    command.arg(&classpath);
-    command.arg(&version_data.main_class);
-
-    command.arg("--username");
-    command.arg(username);
-    command.arg("--version");
-    command.arg(version_id);
-    command.arg("--gameDir");
-    command.arg(game_assets_dir.to_string_lossy().to_string());
-    command.arg("--accessToken");
-    command.arg("0");
-    command.arg("--assetsDir");
-    command.arg(game_assets_dir.join("assets").to_string_lossy().to_string());
-    command.arg("--assetIndex");
-    command.arg(&version_data.asset_index.id);
-    command.arg("--uuid");
-    command.arg("00000000-0000-0000-0000-000000000000");
-    command.arg("--userProperties");
-    command.arg("{}");
-
-    send_progress(
-        LaunchStage::StartingProcess,
-        90,
-        100,
-        "Starting game process...".to_string(),
-    );
-
-    match command.spawn() {
-        Ok(mut child) => {
-            send_progress(
-                LaunchStage::ProcessStarted,
-                95,
-                100,
-                "Process started successfully".to_string(),
+        command.args(&arguments.jvm);
+
+        if let (Some(config_path), Some(config)) = (
+            downloader::download_logging_config(
+                &self.engine,
+                &game_dir,
+                version_data,
+                &self.cancel,
+            )?,
+            version_data
+                .logging
+                .as_ref()
+                .and_then(|logging| logging.client.as_ref()),
+        ) {
+            command.arg(
+                config
+                    .argument
+                    .replace("${path}", &config_path.to_string_lossy()),
             );
+        }
 
-            thread::sleep(Duration::from_millis(2000));
+        command.arg(&version_data.main_class);
+        command.args(&arguments.game);
 
-            match child.try_wait() {
-                Ok(Some(status)) => {
-                    let exit_code = status.code().unwrap_or(-1);
-                    let error_message =
-                        format!("Game process exited immediately with code: {}", exit_code);
+        send_progress(
+            LaunchStage::StartingProcess,
+            90,
+            100,
+            "Starting game process...".to_string(),
+        );
 
-                    send_progress(LaunchStage::Complete, 100, 100, error_message.clone());
+        self.cancel.check()?;
 
-                    return Err(Box::new(io::Error::new(
-                        io::ErrorKind::Other,
-                        error_message,
-                    )));
-                }
-                Ok(None) => {
-                    send_progress(
-                        LaunchStage::LaunchingGame,
-                        98,
-                        100,
-                        "Minecraft is starting...".to_string(),
-                    );
-
-                    for i in 0..5 {
-                        thread::sleep(Duration::from_millis(1000));
-
-                        match child.try_wait() {
-                            Ok(Some(status)) => {
-                                let exit_code = status.code().unwrap_or(-1);
-
-                                if exit_code != 0 {
-                                    let error_message = format!(
-                                        "Game crashed during startup with code: {}",
-                                        exit_code
-                                    );
-                                    send_progress(
-                                        LaunchStage::Complete,
-                                        100,
-                                        100,
-                                        error_message.clone(),
-                                    );
-
-                                    return Err(Box::new(io::Error::new(
-                                        io::ErrorKind::Other,
-                                        error_message,
-                                    )));
-                                } else {
-                                    if i < 2 {
-                                        let warning_message =
-                                            "Minecraft process ended unexpectedly but cleanly";
-                                        send_progress(
-                                            LaunchStage::Complete,
-                                            100,
-                                            100,
-                                            warning_message.to_string(),
-                                        );
-
-                                        return Err(Box::new(io::Error::new(
-                                            io::ErrorKind::Other,
-                                            warning_message,
-                                        )));
-                                    }
-                                }
-                                break;
-                            }
-                            Ok(None) => {
-                                send_progress(
-                                    LaunchStage::LaunchingGame,
-                                    98 + i,
-                                    100,
-                                    format!("Minecraft is loading... ({}s)", i + 1).to_string(),
-                                );
-                            }
-                            Err(e) => {
-                                let error_message =
-                                    format!("Failed to check game process status: {}", e);
-                                send_progress(
-                                    LaunchStage::Complete,
-                                    100,
-                                    100,
-                                    error_message.clone(),
-                                );
-
-                                return Err(Box::new(io::Error::new(
-                                    io::ErrorKind::Other,
-                                    error_message,
-                                )));
-                            }
-                        }
-                    }
-
-                    send_progress(
-                        LaunchStage::Complete,
-                        100,
-                        100,
-                        "Game launched successfully!".to_string(),
-                    );
-
-                    Ok(())
-                }
-                Err(e) => {
-                    let error_message = format!("Failed to check game process status: {}", e);
-                    send_progress(LaunchStage::Complete, 100, 100, error_message.clone());
-
-                    Err(Box::new(io::Error::new(
-                        io::ErrorKind::Other,
-                        error_message,
-                    )))
+        let started_at = SystemTime::now();
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| spawn_error(e, java_executable))?;
+
+        send_progress(
+            LaunchStage::ProcessStarted,
+            95,
+            100,
+            "Process started successfully".to_string(),
+        );
+
+        let output =
+            GameOutput::capture(&mut child, &self.game_dir.join(LOGS_DIR), self.log.clone())?;
+
+        for _ in 0..4 {
+            thread::sleep(Duration::from_millis(500));
+            self.cancelled(&mut child)?;
+        }
+
+        if let Some(status) = child.try_wait().map_err(LaunchError::ProcessStatus)? {
+            return Err(process_exited(status, output, &game_dir, started_at));
+        }
+
+        send_progress(
+            LaunchStage::LaunchingGame,
+            98,
+            100,
+            "Minecraft is starting...".to_string(),
+        );
+
+        for i in 0..5 {
+            thread::sleep(Duration::from_millis(1000));
+            self.cancelled(&mut child)?;
+
+            match child.try_wait().map_err(LaunchError::ProcessStatus)? {
+                Some(status) if !status.success() || i < 2 => {
+                    return Err(process_exited(status, output, &game_dir, started_at));
                 }
+                Some(_) => break,
+                None => send_progress(
+                    LaunchStage::LaunchingGame,
+                    98 + i,
+                    100,
+                    format!("Minecraft is loading... ({}s)", i + 1),
+                ),
             }
         }
-        Err(e) => {
-            let error_message = if e.to_string().contains("No such file or directory") {
-                "Error: Java not found. Please install Java and try again."
-            } else if e.to_string().contains("permission denied") {
-                "Error: Permission denied. Try running as administrator."
-            } else if e.to_string().contains("Too many open files") {
-                "Error: System resource limit reached. Try closing other applications."
-            } else if e.to_string().contains("Cannot allocate memory") {
-                "Error: Not enough memory. Try closing other applications or allocate more memory."
-            } else {
-                &format!("Failed to start game: {}", e)
-            };
-
-            send_progress(LaunchStage::Complete, 100, 100, error_message.to_string());
-
-            Err(Box::new(io::Error::new(
-                io::ErrorKind::Other,
-                error_message,
-            )))
+
+        send_progress(
+            LaunchStage::Complete,
+            100,
+            100,
+            "Game launched successfully!".to_string(),
+        );
+
+        Ok(GameProcess::new(
+            &version_data.id,
+            started_at,
+            output.path(),
+            child,
+            background_download,
+        ))
+    }
+
+    fn send_download_progress(&self, stage: LaunchStage, progress: &JobProgress, message: String) {
+        if let Some(progress_sender) = &self.progress {
+            let _ = progress_sender.send(LaunchProgress {
+                stage,
+                current: progress.completed,
+                total: progress.total,
+                message,
+                bytes: Some(progress.bytes),
+            });
+        }
+    }
+
+    fn cancelled(&self, child: &mut Child) -> Result<(), LaunchError> {
+        if self.cancel.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(LaunchError::Cancelled);
         }
+        Ok(())
+    }
+}
+
+fn spawn_error(error: io::Error, java_executable: PathBuf) -> LaunchError {
+    match error.kind() {
+        io::ErrorKind::NotFound => LaunchError::JavaNotFound(java_executable),
+        io::ErrorKind::PermissionDenied => LaunchError::PermissionDenied(java_executable),
+        _ => LaunchError::Spawn(error),
+    }
+}
+
+fn process_exited(
+    status: ExitStatus,
+    output: GameOutput,
+    game_dir: &Path,
+    started_at: SystemTime,
+) -> LaunchError {
+    let log_tail = output.finish();
+    let crash = Box::new(crash_analyzer::analyze(game_dir, started_at, &log_tail));
+
+    LaunchError::ProcessExited {
+        code: status.code().unwrap_or(-1),
+        log_tail,
+        crash,
     }
 }
\ No newline at end of file
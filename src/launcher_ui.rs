@@ -1,22 +1,43 @@
 use crate::{
-    app_init::get_game_directory,
-    downloader::download_file,
+    app_init::{get_game_directory, get_libraries_directory},
     gui::*,
+};
+use fltk::{
+    app,
+    button::Button,
+    frame::Frame,
+    menu::{Choice, MenuButton, MenuFlag},
+    prelude::*,
+};
+use minecraft_launcher::{
+    Launcher,
+    auth::{AuthEndpoints, MicrosoftAuth},
+    background_jobs::BackgroundJobs,
+    cancel::CancellationToken,
+    error::LaunchError,
+    forge_installer::{ForgeKind, ForgeRepository},
+    game_log::LogLine,
+    install_queue::InstallQueue,
     java_finder::find_compatible_java,
-    launcher::launch_minecraft,
-    models::{self, Profile},
-    profiles::{read_profiles, write_profiles},
-    version_manager::{fetch_version_data, get_version_link},
+    loader_installer::{LoaderKind, LoaderMeta},
+    models::{self, Account, LaunchSession, Profile},
+    process_registry::ProcessRegistry,
+    profiles::{read_profiles, refresh_account, write_profiles},
+    storage::{self, format_size},
+    version_manager::{ManifestService, resolve_version},
+    yggdrasil::YggdrasilAuth,
 };
-use fltk::{app, button::Button, frame::Frame, menu::Choice, prelude::*};
 use std::{
-    fs,
-    path::PathBuf,
-    sync::{Arc, Mutex},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex},
 };
 
+static GAME_PROCESSES: LazyLock<Arc<ProcessRegistry>> =
+    LazyLock::new(|| ProcessRegistry::new(&get_game_directory()));
+static BACKGROUND_JOBS: LazyLock<Arc<BackgroundJobs>> = LazyLock::new(BackgroundJobs::new);
+
 pub fn initialize_profiles(
-    profiles_path: &PathBuf,
+    profiles_path: &Path,
     font: fltk::enums::Font,
 ) -> (Arc<Mutex<Vec<Profile>>>, Vec<String>) {
     let profiles = Arc::new(Mutex::new(match read_profiles(&profiles_path) {
@@ -101,21 +122,740 @@ pub fn setup_profile_callbacks(
     }
 }
 
+pub fn setup_account_menu(
+    mut tools_menu: MenuButton,
+    profiles: Arc<Mutex<Vec<Profile>>>,
+    profiles_path: Arc<PathBuf>,
+    profile_choice: Choice,
+    font: fltk::enums::Font,
+) {
+    {
+        let profiles = profiles.clone();
+        let profiles_path = profiles_path.clone();
+        let profile_choice = profile_choice.clone();
+        tools_menu.add(
+            "Microsoft Login...",
+            fltk::enums::Shortcut::None,
+            MenuFlag::Normal,
+            move |_| {
+                microsoft_login(&profiles, &profiles_path, &profile_choice, font);
+            },
+        );
+    }
+
+    {
+        let profiles = profiles.clone();
+        let profiles_path = profiles_path.clone();
+        let profile_choice = profile_choice.clone();
+        tools_menu.add(
+            "Auth Server Login...",
+            fltk::enums::Shortcut::None,
+            MenuFlag::Normal,
+            move |_| {
+                auth_server_login(&profiles, &profiles_path, &profile_choice, font);
+            },
+        );
+    }
+
+    {
+        let profiles = profiles.clone();
+        let profiles_path = profiles_path.clone();
+        let profile_choice = profile_choice.clone();
+        tools_menu.add(
+            "Sign Out Account",
+            fltk::enums::Shortcut::None,
+            MenuFlag::MenuDivider,
+            move |_| {
+                sign_out(&profiles, &profiles_path, &profile_choice, font);
+            },
+        );
+    }
+}
+
+pub fn setup_tools_menu(
+    mut tools_menu: MenuButton,
+    version_choice: Choice,
+    versions: VersionList,
+    manifest: Arc<ManifestService>,
+    java_choice: Choice,
+    java_installations: Vec<(PathBuf, String)>,
+    font: fltk::enums::Font,
+) {
+    let java_installations = Arc::new(java_installations);
+    let install_queue = InstallQueue::new(
+        &get_game_directory(),
+        &get_libraries_directory(),
+        manifest.clone(),
+    );
+
+    {
+        let version_choice = version_choice.clone();
+        let install_queue = install_queue.clone();
+        tools_menu.add(
+            "Install Selected Version",
+            fltk::enums::Shortcut::None,
+            MenuFlag::Normal,
+            move |_| {
+                let Some(version_id) = version_choice.choice() else {
+                    show_error_dialog("Please, choose version Minecraft!", font);
+                    return;
+                };
+                install_queue.enqueue(&version_id);
+                download_manager_window(font, install_queue.clone());
+            },
+        );
+    }
+
+    {
+        let install_queue = install_queue.clone();
+        tools_menu.add(
+            "Storage Manager...",
+            fltk::enums::Shortcut::None,
+            MenuFlag::Normal,
+            move |_| {
+                let state = Arc::new(Mutex::new(StorageState::default()));
+                let install_queue = install_queue.clone();
+                storage_window(font, state.clone(), move |action| {
+                    run_storage_action(action, &state, &install_queue);
+                });
+            },
+        );
+    }
+
+    {
+        let install_queue = install_queue.clone();
+        tools_menu.add(
+            "Download Manager...",
+            fltk::enums::Shortcut::None,
+            MenuFlag::MenuDivider,
+            move |_| {
+                download_manager_window(font, install_queue.clone());
+            },
+        );
+    }
+
+    {
+        let version_choice = version_choice.clone();
+        let manifest = manifest.clone();
+        tools_menu.add(
+            "Verify && Repair Selected Version...",
+            fltk::enums::Shortcut::None,
+            MenuFlag::Normal,
+            move |_| {
+                verify_version(&version_choice, &manifest, false, font);
+            },
+        );
+    }
+
+    for kind in [LoaderKind::Fabric, LoaderKind::Quilt] {
+        let version_choice = version_choice.clone();
+        let versions = versions.clone();
+        tools_menu.add(
+            &format!("Install {}...", kind.name()),
+            fltk::enums::Shortcut::None,
+            MenuFlag::Normal,
+            move |_| {
+                install_loader(kind, &version_choice, &versions, font);
+            },
+        );
+    }
+
+    for kind in [ForgeKind::Forge, ForgeKind::NeoForge] {
+        let version_choice = version_choice.clone();
+        let versions = versions.clone();
+        let manifest = manifest.clone();
+        let java_choice = java_choice.clone();
+        let java_installations = java_installations.clone();
+        tools_menu.add(
+            &format!("Install {}...", kind.name()),
+            fltk::enums::Shortcut::None,
+            if kind == ForgeKind::NeoForge {
+                MenuFlag::MenuDivider
+            } else {
+                MenuFlag::Normal
+            },
+            move |_| {
+                install_forge(
+                    kind,
+                    &version_choice,
+                    &versions,
+                    &manifest,
+                    selected_java(&java_choice, &java_installations),
+                    font,
+                );
+            },
+        );
+    }
+
+    tools_menu.add(
+        "Log Console...",
+        fltk::enums::Shortcut::None,
+        MenuFlag::Normal,
+        move |_| {
+            log_console_window(font);
+        },
+    );
+
+    tools_menu.add(
+        "Background Downloads...",
+        fltk::enums::Shortcut::None,
+        MenuFlag::Normal,
+        move |_| {
+            background_downloads_window(font, BACKGROUND_JOBS.clone());
+        },
+    );
+
+    tools_menu.add(
+        "Running Instances...",
+        fltk::enums::Shortcut::None,
+        MenuFlag::Normal,
+        move |_| {
+            running_instances_window(font, GAME_PROCESSES.clone());
+        },
+    );
+
+    tools_menu.add(
+        "Kill Game",
+        fltk::enums::Shortcut::None,
+        MenuFlag::Normal,
+        move |_| {
+            let Some(instance) = GAME_PROCESSES.running().pop() else {
+                show_error_dialog("No game is running", font);
+                return;
+            };
+            if let Err(e) = GAME_PROCESSES.kill(instance.pid) {
+                show_error_dialog(&format!("Failed to stop the game: {}", e), font);
+            }
+        },
+    );
+}
+
+fn microsoft_login(
+    profiles: &Arc<Mutex<Vec<Profile>>>,
+    profiles_path: &Path,
+    profile_choice: &Choice,
+    font: fltk::enums::Font,
+) {
+    let selected_idx = profile_choice.value() as usize;
+    if selected_idx >= profiles.lock().unwrap().len() {
+        show_error_dialog("Please select a profile!", font);
+        return;
+    }
+
+    let result = microsoft_login_dialog(font, |show_code| {
+        let auth = MicrosoftAuth::new(AuthEndpoints::new());
+        let device_code = auth
+            .request_device_code()
+            .map_err(|e| format!("Failed to start Microsoft login: {}", e))?;
+        show_code(&device_code.verification_uri, &device_code.user_code);
+        auth.poll_device_code(&device_code)
+            .map_err(|e| e.to_string())
+    });
+
+    match result {
+        Some(Ok(account)) => {
+            profiles.lock().unwrap()[selected_idx].account = Some(Account::Microsoft(account));
+
+            if let Err(e) = write_profiles(profiles_path, &profiles.lock().unwrap()) {
+                show_error_dialog(&format!("Failed to save profiles: {}", e), font);
+            }
+        }
+        Some(Err(e)) => show_error_dialog(&e, font),
+        None => {}
+    }
+}
+
+fn auth_server_login(
+    profiles: &Arc<Mutex<Vec<Profile>>>,
+    profiles_path: &Path,
+    profile_choice: &Choice,
+    font: fltk::enums::Font,
+) {
+    let selected_idx = profile_choice.value() as usize;
+    if selected_idx >= profiles.lock().unwrap().len() {
+        show_error_dialog("Please select a profile!", font);
+        return;
+    }
+
+    let account = auth_server_login_dialog(font, |login| {
+        YggdrasilAuth::new(&login.server_url)
+            .authenticate(&login.username, &login.password)
+            .map_err(|e| e.to_string())
+    });
+
+    if let Some(account) = account {
+        profiles.lock().unwrap()[selected_idx].account = Some(Account::Yggdrasil(account));
+
+        if let Err(e) = write_profiles(profiles_path, &profiles.lock().unwrap()) {
+            show_error_dialog(&format!("Failed to save profiles: {}", e), font);
+        }
+    }
+}
+
+fn sign_out(
+    profiles: &Arc<Mutex<Vec<Profile>>>,
+    profiles_path: &Path,
+    profile_choice: &Choice,
+    font: fltk::enums::Font,
+) {
+    let selected_idx = profile_choice.value() as usize;
+    if selected_idx >= profiles.lock().unwrap().len() {
+        show_error_dialog("Please select a profile!", font);
+        return;
+    }
+
+    profiles.lock().unwrap()[selected_idx].account = None;
+
+    if let Err(e) = write_profiles(profiles_path, &profiles.lock().unwrap()) {
+        show_error_dialog(&format!("Failed to save profiles: {}", e), font);
+    }
+}
+
+fn verify_version(
+    version_choice: &Choice,
+    manifest: &Arc<ManifestService>,
+    repair: bool,
+    font: fltk::enums::Font,
+) {
+    let Some(version_id) = version_choice.choice() else {
+        show_error_dialog("Please, choose version Minecraft!", font);
+        return;
+    };
+
+    let state = Arc::new(Mutex::new(VerifyState::default()));
+    verify_window(font, &version_id, repair, state.clone(), {
+        let version_id = version_id.clone();
+        let manifest = manifest.clone();
+        move |repair| run_verify(&version_id, &manifest, &state, repair)
+    });
+}
+
+fn run_verify(
+    version_id: &str,
+    manifest: &Arc<ManifestService>,
+    state: &Arc<Mutex<VerifyState>>,
+    repair: bool,
+) {
+    let previous = {
+        let mut state = state.lock().unwrap();
+        if state.running {
+            return;
+        }
+        state.running = true;
+        state.error = None;
+        state.progress = None;
+        state.report.clone()
+    };
+
+    let version_id = version_id.to_string();
+    let manifest = manifest.clone();
+    let state = state.clone();
+    std::thread::spawn(move || {
+        let (sender, receiver) = std::sync::mpsc::channel::<models::LaunchProgress>();
+        let progress_state = state.clone();
+        let progress_thread = std::thread::spawn(move || {
+            for progress in receiver {
+                progress_state.lock().unwrap().progress = Some(progress);
+            }
+        });
+
+        let launcher = Launcher::builder(get_game_directory())
+            .cache_dir(get_libraries_directory())
+            .manifest(manifest)
+            .progress(sender)
+            .build();
+        let result = launcher
+            .resolve_version(&version_id)
+            .map_err(|e| format!("Failed to load version {}: {}", version_id, e))
+            .and_then(|version_data| {
+                match previous {
+                    Some(report) if repair => launcher.repair(&version_data, &report),
+                    None if repair => launcher
+                        .verify(&version_data)
+                        .and_then(|report| launcher.repair(&version_data, &report)),
+                    _ => launcher.verify(&version_data),
+                }
+                .map_err(|e| e.to_string())
+            });
+        drop(launcher);
+        let _ = progress_thread.join();
+
+        let mut state = state.lock().unwrap();
+        match result {
+            Ok(report) => state.report = Some(report),
+            Err(e) => state.error = Some(e),
+        }
+        state.running = false;
+        state.revision += 1;
+    });
+}
+
+fn storage_busy(install_queue: &InstallQueue) -> bool {
+    !GAME_PROCESSES.running().is_empty()
+        || BACKGROUND_JOBS
+            .list()
+            .iter()
+            .any(|job| job.status().running)
+        || install_queue
+            .entries()
+            .iter()
+            .any(|entry| !entry.state.is_finished())
+}
+
+fn run_storage_action(
+    action: StorageAction,
+    state: &Arc<Mutex<StorageState>>,
+    install_queue: &InstallQueue,
+) {
+    let deletes = matches!(
+        action,
+        StorageAction::DeleteUnused | StorageAction::RemoveVersion(_)
+    );
+    let plan = {
+        let mut state = state.lock().unwrap();
+        if state.running {
+            return;
+        }
+        if deletes && storage_busy(install_queue) {
+            state.message =
+                "Close the game and wait for downloads to finish before deleting files".to_string();
+            return;
+        }
+        state.running = true;
+        state.message = match &action {
+            StorageAction::Refresh => "Calculating disk usage...".to_string(),
+            StorageAction::FindUnused => "Looking for unused files...".to_string(),
+            StorageAction::DeleteUnused => "Deleting unused files...".to_string(),
+            StorageAction::RemoveVersion(version_id) => format!("Removing {}...", version_id),
+        };
+        state.plan.clone()
+    };
+
+    let game_dir = get_game_directory();
+    let libraries_dir = get_libraries_directory();
+    let state = state.clone();
+    std::thread::spawn(move || {
+        let (plan, message) = match action {
+            StorageAction::Refresh => (plan, String::new()),
+            StorageAction::FindUnused => {
+                let plan = storage::find_unused(&game_dir, &libraries_dir);
+                let message = plan.warnings.first().cloned().unwrap_or_else(|| {
+                    format!(
+                        "{} unused files, {} can be freed",
+                        plan.files.len(),
+                        format_size(plan.bytes())
+                    )
+                });
+                (Some(plan), message)
+            }
+            StorageAction::DeleteUnused => match &plan {
+                Some(unused) => {
+                    let result = storage::collect_garbage(&game_dir, &libraries_dir, unused);
+                    let message = if result.failed.is_empty() {
+                        format!(
+                            "Removed {} files, freed {}",
+                            result.removed,
+                            format_size(result.bytes)
+                        )
+                    } else {
+                        format!(
+                            "Removed {} files, {} could not be removed",
+                            result.removed,
+                            result.failed.len()
+                        )
+                    };
+                    (None, message)
+                }
+                None => (None, String::new()),
+            },
+            StorageAction::RemoveVersion(version_id) => {
+                match storage::remove_version(&game_dir, &version_id) {
+                    Ok(()) => (
+                        None,
+                        format!(
+                            "Removed {}, use Find Unused to clean up its files",
+                            version_id
+                        ),
+                    ),
+                    Err(e) => (plan, e.to_string()),
+                }
+            }
+        };
+
+        let usage = storage::disk_usage(&game_dir, &libraries_dir);
+        let mut state = state.lock().unwrap();
+        state.usage = Some(usage);
+        state.plan = plan;
+        state.message = message;
+        state.running = false;
+        state.revision += 1;
+    });
+}
+
+fn install_forge(
+    kind: ForgeKind,
+    version_choice: &Choice,
+    versions: &VersionList,
+    manifest: &Arc<ManifestService>,
+    selected_java: Option<PathBuf>,
+    font: fltk::enums::Font,
+) {
+    let game_version = match version_choice.choice() {
+        Some(id) => id,
+        None => {
+            show_error_dialog("Please, choose version Minecraft!", font);
+            return;
+        }
+    };
+
+    let repository = ForgeRepository::new(kind);
+    let version_choice = version_choice.clone();
+    let versions = versions.clone();
+    let manifest = manifest.clone();
+    fetch_in_background(
+        {
+            let game_version = game_version.clone();
+            move || {
+                let result = repository
+                    .fetch_versions(&game_version)
+                    .map_err(|e| e.to_string());
+                (repository, result)
+            }
+        },
+        move |(repository, result)| {
+            let forge_versions = match result {
+                Ok(forge_versions) if !forge_versions.is_empty() => forge_versions,
+                Ok(_) => {
+                    show_error_dialog(
+                        &format!("{} is not available for {}", kind.name(), game_version),
+                        font,
+                    );
+                    return;
+                }
+                Err(e) => {
+                    show_error_dialog(
+                        &format!("Failed to get {} versions: {}", kind.name(), e),
+                        font,
+                    );
+                    return;
+                }
+            };
+
+            let installed =
+                install_loader_dialog(font, kind.name(), &game_version, &forge_versions, {
+                    let game_version = game_version.clone();
+                    move |forge_version| {
+                        let game_dir = get_game_directory();
+                        let java_path = selected_java
+                            .clone()
+                            .or_else(|| {
+                                resolve_version(&game_dir, &manifest, &game_version)
+                                    .ok()
+                                    .and_then(|vanilla| vanilla.get_required_java_version())
+                                    .and_then(|required| find_compatible_java(required, false))
+                            })
+                            .unwrap_or_else(|| PathBuf::from("java"));
+
+                        repository
+                            .install(
+                                &game_dir,
+                                &get_libraries_directory(),
+                                &java_path,
+                                &manifest,
+                                &game_version,
+                                &forge_version,
+                            )
+                            .map_err(|e| e.to_string())
+                    }
+                });
+
+            if let Some(version_id) = installed {
+                select_installed_version(&version_choice, &versions, &version_id);
+            }
+        },
+    );
+}
+
+fn install_loader(
+    kind: LoaderKind,
+    version_choice: &Choice,
+    versions: &VersionList,
+    font: fltk::enums::Font,
+) {
+    let game_version = match version_choice.choice() {
+        Some(id) => id,
+        None => {
+            show_error_dialog("Please, choose version Minecraft!", font);
+            return;
+        }
+    };
+
+    let meta = LoaderMeta::new(kind);
+    let version_choice = version_choice.clone();
+    let versions = versions.clone();
+    fetch_in_background(
+        {
+            let game_version = game_version.clone();
+            move || {
+                let result = meta
+                    .fetch_loader_versions(&game_version)
+                    .map_err(|e| e.to_string());
+                (meta, result)
+            }
+        },
+        move |(meta, result)| {
+            let loader_versions = match result {
+                Ok(loader_versions) if !loader_versions.is_empty() => loader_versions,
+                Ok(_) => {
+                    show_error_dialog(
+                        &format!("{} is not available for {}", kind.name(), game_version),
+                        font,
+                    );
+                    return;
+                }
+                Err(e) => {
+                    show_error_dialog(
+                        &format!("Failed to get {} versions: {}", kind.name(), e),
+                        font,
+                    );
+                    return;
+                }
+            };
+
+            let loader_version_names: Vec<String> = loader_versions
+                .iter()
+                .map(|loader| loader.version.clone())
+                .collect();
+
+            let installed =
+                install_loader_dialog(font, kind.name(), &game_version, &loader_version_names, {
+                    let game_version = game_version.clone();
+                    move |loader_version| {
+                        meta.install(
+                            &get_game_directory(),
+                            &get_libraries_directory(),
+                            &game_version,
+                            &loader_version,
+                        )
+                        .map_err(|e| e.to_string())
+                    }
+                });
+
+            if let Some(version_id) = installed {
+                select_installed_version(&version_choice, &versions, &version_id);
+            }
+        },
+    );
+}
+
+fn fetch_in_background<T: Send + 'static>(
+    fetch: impl FnOnce() -> T + Send + 'static,
+    on_done: impl FnOnce(T) + 'static,
+) {
+    let (sender, receiver) = std::sync::mpsc::channel::<T>();
+    std::thread::spawn(move || {
+        let _ = sender.send(fetch());
+        app::awake();
+    });
+
+    let mut on_done = Some(on_done);
+    app::add_timeout3(0.05, move |handle| match receiver.try_recv() {
+        Ok(value) => {
+            if let Some(on_done) = on_done.take() {
+                on_done(value);
+            }
+        }
+        Err(std::sync::mpsc::TryRecvError::Empty) => app::repeat_timeout3(0.05, handle),
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+    });
+}
+
+fn selected_java(
+    java_choice: &Choice,
+    java_installations: &[(PathBuf, String)],
+) -> Option<PathBuf> {
+    java_installations
+        .get(java_choice.value() as usize)
+        .map(|(path, _)| path.clone())
+}
+
+fn choose_java(java_choice: &Choice, has_java: bool, font: fltk::enums::Font) {
+    if !has_java {
+        show_error_dialog(
+            "No Java installations were found. Install Java and restart the launcher.",
+            font,
+        );
+        return;
+    }
+
+    let Some(menu) = java_choice.at(0) else {
+        return;
+    };
+    if let Some(item) = menu.pulldown(
+        java_choice.x(),
+        java_choice.y(),
+        java_choice.w(),
+        java_choice.h(),
+        java_choice.mvalue(),
+        Some(java_choice),
+    ) {
+        java_choice.clone().set_item(&item);
+    }
+}
+
+fn select_installed_version(version_choice: &Choice, versions: &VersionList, version_id: &str) {
+    versions.add(version_id, "release");
+
+    let mut version_choice = version_choice.clone();
+    let index = version_choice.find_index(version_id);
+    if index >= 0 {
+        version_choice.set_value(index);
+    }
+}
+
 pub fn setup_play_button_callback(
     mut play_button: Button,
+    cancel_button: Button,
     profiles: Arc<Mutex<Vec<Profile>>>,
+    profiles_path: Arc<PathBuf>,
+    manifest: Arc<ManifestService>,
     version_choice: Choice,
     profile_choice: Choice,
     java_choice: Choice,
     status_label: Frame,
     progress_bar: Frame,
     java_installations: Vec<(std::path::PathBuf, String)>,
-    error_message: Arc<Mutex<Option<String>>>,
+    error_message: Arc<Mutex<Option<ErrorReport>>>,
     font: fltk::enums::Font,
 ) {
     let profiles_clone = profiles.clone();
 
-    play_button.set_callback(move |_| {
+    GAME_PROCESSES.on_crash({
+        let error_message = error_message.clone();
+        move |error| {
+            *error_message.lock().unwrap() = Some(error.into());
+            app::awake();
+        }
+    });
+
+    set_recovery_handler({
+        let play_button = play_button.clone();
+        let java_choice = java_choice.clone();
+        let has_java = !java_installations.is_empty();
+        let version_choice = version_choice.clone();
+        let manifest = manifest.clone();
+        move |action| match action {
+            RecoveryAction::Retry => {
+                if play_button.active() {
+                    play_button.clone().do_callback();
+                }
+            }
+            RecoveryAction::ChooseJava => choose_java(&java_choice, has_java, font),
+            RecoveryAction::Repair => verify_version(&version_choice, &manifest, true, font),
+        }
+    });
+
+    play_button.set_callback(move |play_button| {
         let username = match profile_choice.choice() {
             Some(selected) => selected,
             None => {
@@ -132,18 +872,8 @@ pub fn setup_play_button_callback(
             }
         };
 
-        let version_json_url = match get_version_link(version_id.clone()) {
-            Some(url) => url,
-            None => {
-                show_error_dialog(
-                    &format!("Failed to get URL for version {}", version_id),
-                    font,
-                );
-                return;
-            }
-        };
-
-        let version_data = match fetch_version_data(&version_json_url) {
+        let game_dir = get_game_directory();
+        let version_data = match resolve_version(&game_dir, &manifest, &version_id) {
             Ok(data) => data,
             Err(e) => {
                 show_error_dialog(&format!("Failed to get data version: {}", e), font);
@@ -151,39 +881,8 @@ pub fn setup_play_button_callback(
             }
         };
 
-        let mut jar_path = get_game_directory();
-        jar_path.push("versions");
-        jar_path.push(&version_id);
-
-        if let Err(e) = fs::create_dir_all(&jar_path) {
-            show_error_dialog(
-                &format!("Failed to create directory {}: {}", jar_path.display(), e),
-                font,
-            );
-            return;
-        }
-
-        jar_path.push("client.jar");
-        if !jar_path.exists() {
-            if let Err(e) = download_file(&version_data.downloads.client.url, &jar_path) {
-                show_error_dialog(&format!("Failed to loading client.jar: {}", e), font);
-                return;
-            }
-        }
-
-        let selected_java_path = if !java_installations.is_empty() {
-            let selected_index = java_choice.value() as usize;
-            if selected_index < java_installations.len() {
-                Some(java_installations[selected_index].0.clone())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
         let mut status_label_clone = status_label.clone();
-        let java_path_to_use = match selected_java_path {
+        let java_path_to_use = match selected_java(&java_choice, &java_installations) {
             Some(path) => Some(path),
             None => {
                 if let Some(required_version) = version_data.get_required_java_version() {
@@ -217,22 +916,54 @@ pub fn setup_play_button_callback(
         app::redraw();
         app::flush();
 
+        let cancel = CancellationToken::new();
+        let mut cancel_button = cancel_button.clone();
+        cancel_button.set_callback({
+            let cancel = cancel.clone();
+            let mut status_label = status_label.clone();
+            move |cancel_button| {
+                cancel.cancel();
+                cancel_button.deactivate();
+                status_label.set_label("Cancelling...");
+            }
+        });
+        cancel_button.activate();
+        play_button.deactivate();
+
         let (sender, receiver) = std::sync::mpsc::channel::<models::LaunchProgress>();
 
         setup_progress_monitoring(
             receiver,
             progress_bar.clone(),
             status_label.clone(),
-            error_message.clone(),
+            play_button.clone(),
+            cancel_button,
         );
 
+        let (log_sender, log_receiver) = std::sync::mpsc::channel::<LogLine>();
+        GAME_LOG.lock().unwrap().clear();
+        std::thread::spawn(move || {
+            for line in log_receiver {
+                GAME_LOG.lock().unwrap().push(line);
+            }
+        });
+
+        let launcher = Launcher::builder(game_dir)
+            .cache_dir(get_libraries_directory())
+            .manifest(manifest.clone())
+            .progress(sender)
+            .log(log_sender)
+            .cancel(cancel)
+            .background_jobs(BACKGROUND_JOBS.clone())
+            .build();
+
         launch_minecraft_process(
-            version_id,
             username,
             version_data,
             java_path_to_use,
             profiles_clone.clone(),
-            sender,
+            profiles_path.clone(),
+            launcher,
             error_message.clone(),
         );
     });
@@ -242,12 +973,12 @@ fn setup_progress_monitoring(
     receiver: std::sync::mpsc::Receiver<models::LaunchProgress>,
     progress_bar: Frame,
     status_label: Frame,
-    error_msg: Arc<Mutex<Option<String>>>,
+    mut play_button: Button,
+    mut cancel_button: Button,
 ) {
     app::add_timeout3(0.05, {
         let mut progress_bar_clone = progress_bar.clone();
         let mut status_label_clone = status_label.clone();
-        let error_msg_clone = error_msg.clone();
 
         move |handle| {
             match receiver.try_recv() {
@@ -256,23 +987,17 @@ fn setup_progress_monitoring(
 
                     update_win98_progress_bar(&mut progress_bar_clone, percentage);
 
-                    let status_text = progress.message.clone();
-
-                    if progress.stage == models::LaunchStage::Complete {
-                        let error_keywords =
-                            ["error", "failed", "crashed", "exited", "unexpectedly"];
-
-                        if error_keywords
-                            .iter()
-                            .any(|&keyword| status_text.to_lowercase().contains(keyword))
-                        {
-                            let mut error = error_msg_clone.lock().unwrap();
-                            *error = Some(status_text.clone());
-
-                            app::awake();
+                    let status_text = match &progress.bytes {
+                        Some(bytes) if bytes.total > 0 => {
+                            format!("{} - {}", bytes.format(), progress.message)
                         }
+                        _ => progress.message.clone(),
+                    };
 
+                    if progress.stage == models::LaunchStage::Complete {
                         progress_bar_clone.hide();
+                        play_button.activate();
+                        cancel_button.deactivate();
                     }
 
                     status_label_clone.set_label(&status_text);
@@ -281,6 +1006,8 @@ fn setup_progress_monitoring(
                 Err(std::sync::mpsc::TryRecvError::Empty) => {}
                 Err(std::sync::mpsc::TryRecvError::Disconnected) => {
                     progress_bar_clone.hide();
+                    play_button.activate();
+                    cancel_button.deactivate();
                     app::redraw();
                     return;
                 }
@@ -292,45 +1019,87 @@ fn setup_progress_monitoring(
 }
 
 fn launch_minecraft_process(
-    version_id: String,
     username: String,
-    version_data: crate::models::VersionData,
+    version_data: minecraft_launcher::models::VersionData,
     java_path: Option<std::path::PathBuf>,
     profiles: Arc<Mutex<Vec<Profile>>>,
-    sender: std::sync::mpsc::Sender<models::LaunchProgress>,
-    error_msg: Arc<Mutex<Option<String>>>,
+    profiles_path: Arc<PathBuf>,
+    launcher: Launcher,
+    error_msg: Arc<Mutex<Option<ErrorReport>>>,
 ) {
     let error_msg_clone = error_msg.clone();
     let profiles_clone = profiles.clone();
 
     std::thread::spawn(move || {
-        let jvm_args = profiles_clone
+        let profile = profiles_clone
             .lock()
             .unwrap()
             .iter()
             .find(|p| p.username == username)
-            .and_then(|profile| profile.jvm_args.clone())
-            .map(|args_str| {
-                args_str
-                    .split_whitespace()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>()
+            .cloned()
+            .unwrap_or_else(|| Profile {
+                username: username.clone(),
+                jvm_args: None,
+                uuid: None,
+                account: None,
             });
 
-        if let Err(e) = launch_minecraft(
-            &version_id,
-            &username,
-            &version_data,
-            java_path,
-            jvm_args,
-            Some(sender),
-        ) {
-            let error_text = format!("{}", e);
+        let jvm_args = profile.jvm_args.as_ref().map(|args_str| {
+            args_str
+                .split_whitespace()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+        });
+
+        let session = match &profile.account {
+            Some(account) => match refresh_account(account) {
+                Ok((refreshed, session)) => {
+                    if refreshed != *account {
+                        let mut profiles = profiles_clone.lock().unwrap();
+                        if let Some(profile) = profiles.iter_mut().find(|p| p.username == username)
+                        {
+                            profile.account = Some(refreshed);
+                        }
+                        let _ = write_profiles(&profiles_path, &profiles);
+                    }
+                    session
+                }
+                Err(e) => {
+                    let mut error = error_msg_clone.lock().unwrap();
+                    *error = Some(format!("Login expired, please log in again: {}", e).into());
+
+                    app::awake();
+                    return;
+                }
+            },
+            None => LaunchSession::offline(&profile),
+        };
+
+        match launcher.launch(&version_data, &session, java_path, jvm_args) {
+            Ok(process) => {
+                let background_download = process.background_download().cloned();
+                GAME_PROCESSES.register(&username, process);
 
-            let mut error = error_msg_clone.lock().unwrap();
-            *error = Some(error_text);
+                if let Some(job) = background_download {
+                    job.wait();
+                    let status = job.status();
+                    if !status.failures.is_empty() {
+                        launcher.report(format!(
+                            "{} of {} game assets failed to download. Open Tools > Background Downloads to retry.",
+                            status.failures.len(),
+                            status.total
+                        ));
+                        app::awake();
+                    }
+                }
+            }
+            Err(LaunchError::Cancelled) => {}
+            Err(e) => {
+                let mut error = error_msg_clone.lock().unwrap();
+                *error = Some(e.into());
 
-            app::awake();
+                app::awake();
+            }
         }
     });
 }
\ No newline at end of file
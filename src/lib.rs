@@ -0,0 +1,23 @@
+pub mod arguments;
+pub mod auth;
+pub mod background_jobs;
+pub mod cancel;
+pub mod crash_analyzer;
+pub mod download_engine;
+pub mod downloader;
+pub mod error;
+pub mod forge_installer;
+pub mod game_log;
+pub mod install_queue;
+pub mod integrity;
+pub mod java_finder;
+pub mod launcher;
+pub mod loader_installer;
+pub mod models;
+pub mod process_registry;
+pub mod profiles;
+pub mod storage;
+pub mod version_manager;
+pub mod yggdrasil;
+
+pub use launcher::{Launcher, LauncherBuilder};
\ No newline at end of file
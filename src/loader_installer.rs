@@ -0,0 +1,146 @@
+use crate::{
+    cancel::CancellationToken, download_engine::DownloadEngine, downloader, models::VersionData,
+    version_manager::local_version_path,
+};
+use serde::Deserialize;
+use std::{error::Error, fs, path::Path};
+
+const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoaderKind {
+    Fabric,
+    Quilt,
+}
+
+impl LoaderKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoaderKind::Fabric => "Fabric",
+            LoaderKind::Quilt => "Quilt",
+        }
+    }
+
+    fn default_meta_url(&self) -> &'static str {
+        match self {
+            LoaderKind::Fabric => FABRIC_META_URL,
+            LoaderKind::Quilt => QUILT_META_URL,
+        }
+    }
+
+    fn meta_url_env(&self) -> &'static str {
+        match self {
+            LoaderKind::Fabric => "FABRIC_META_URL",
+            LoaderKind::Quilt => "QUILT_META_URL",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LoaderEntry {
+    loader: LoaderVersion,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LoaderVersion {
+    pub version: String,
+}
+
+pub struct LoaderMeta {
+    pub kind: LoaderKind,
+    pub base_url: String,
+}
+
+impl LoaderMeta {
+    pub fn new(kind: LoaderKind) -> Self {
+        let base_url = std::env::var(kind.meta_url_env())
+            .unwrap_or_else(|_| kind.default_meta_url().to_string());
+        Self::with_base_url(kind, &base_url)
+    }
+
+    pub fn with_base_url(kind: LoaderKind, base_url: &str) -> Self {
+        LoaderMeta {
+            kind,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn fetch_loader_versions(
+        &self,
+        game_version: &str,
+    ) -> Result<Vec<LoaderVersion>, Box<dyn Error>> {
+        let url = format!("{}/versions/loader/{}", self.base_url, game_version);
+        let response = DownloadEngine::shared().client().get(&url).send()?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "{} meta returned {} for {}",
+                self.kind.name(),
+                response.status(),
+                game_version
+            )
+            .into());
+        }
+
+        let entries: Vec<LoaderEntry> = response.json()?;
+        Ok(entries.into_iter().map(|entry| entry.loader).collect())
+    }
+
+    pub fn install(
+        &self,
+        game_dir: &Path,
+        libraries_dir: &Path,
+        game_version: &str,
+        loader_version: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let url = format!(
+            "{}/versions/loader/{}/{}/profile/json",
+            self.base_url, game_version, loader_version
+        );
+        let response = DownloadEngine::shared().client().get(&url).send()?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Failed to fetch {} profile: {}",
+                self.kind.name(),
+                response.status()
+            )
+            .into());
+        }
+
+        let profile_json = response.text()?;
+        let version_data: VersionData = serde_json::from_str(&profile_json)?;
+        if version_data.id.is_empty() {
+            return Err(format!("{} profile has no version id", self.kind.name()).into());
+        }
+
+        let expected_libraries = version_data
+            .libraries
+            .iter()
+            .filter(|library| library.artifact().is_some())
+            .count();
+        let classpath = downloader::download_libraries(
+            DownloadEngine::shared(),
+            &version_data.libraries,
+            libraries_dir,
+            None,
+            &CancellationToken::new(),
+        )?;
+        if classpath.len() < expected_libraries {
+            return Err(format!(
+                "Failed to download {} of {} {} libraries",
+                expected_libraries - classpath.len(),
+                expected_libraries,
+                self.kind.name()
+            )
+            .into());
+        }
+
+        let version_path = local_version_path(game_dir, &version_data.id);
+        if let Some(parent) = version_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&version_path, profile_json)?;
+
+        Ok(version_data.id)
+    }
+}
\ No newline at end of file
@@ -4,27 +4,39 @@ use fltk::{app, prelude::*};
 use std::sync::Arc;
 
 mod app_init;
-mod downloader;
+mod cli;
 mod gui;
-mod java_finder;
-mod launcher;
 mod launcher_ui;
-mod models;
-mod profiles;
-mod version_manager;
 mod window_manager;
 
 use app_init::{get_game_directory, setup_error_handler};
-use java_finder::find_all_java_installations;
-use launcher_ui::{initialize_profiles, setup_play_button_callback, setup_profile_callbacks};
-use version_manager::get_version_ids;
+use launcher_ui::{
+    initialize_profiles, setup_account_menu, setup_play_button_callback, setup_profile_callbacks,
+    setup_tools_menu,
+};
+use minecraft_launcher::{
+    java_finder::find_all_java_installations,
+    version_manager::{ManifestService, get_version_list},
+};
 use window_manager::{finalize_window, setup_window};
 
 #[cfg(target_os = "windows")]
 mod windows;
 
 fn main() {
-    let version_ids = get_version_ids();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        #[cfg(target_os = "windows")]
+        windows::attach_console();
+
+        if let Some(code) = cli::run(&args) {
+            std::process::exit(code);
+        }
+    }
+
+    let game_dir = get_game_directory();
+    let manifest = Arc::new(ManifestService::new(&game_dir));
+    let versions = get_version_list(&game_dir, &manifest);
 
     let (error_message, _font_for_error) = setup_error_handler();
 
@@ -33,7 +45,6 @@ fn main() {
 
     let win = setup_window(font);
 
-    let game_dir = get_game_directory();
     let profiles_path = game_dir.join("profiles.json");
 
     let (profiles, profile_names) = initialize_profiles(&profiles_path, font);
@@ -41,39 +52,59 @@ fn main() {
 
     let java_installations = find_all_java_installations();
 
-    let (
-        version_choice,
-        mut profile_choice,
-        play_button,
-        new_profile_button,
-        edit_profile_button,
-        _welcome_frame,
-        java_choice,
-        status_label,
-        progress_bar,
-    ) = gui::setup_main_controls(font, version_ids, &profile_names);
+    let controls = gui::setup_main_controls(font, versions, &profile_names);
+    let mut profile_choice = controls.profile_choice;
 
     if !profiles.lock().unwrap().is_empty() {
         profile_choice.set_value(0);
     }
 
+    if let Some(latest_release) = manifest.latest_release() {
+        let mut version_choice = controls.version_choice.clone();
+        let index = version_choice.find_index(&latest_release);
+        if index >= 0 {
+            version_choice.set_value(index);
+        }
+    }
+
     setup_profile_callbacks(
         profiles.clone(),
         profiles_path.clone(),
         profile_choice.clone(),
-        new_profile_button,
-        edit_profile_button,
+        controls.new_profile_button,
+        controls.edit_profile_button,
+        font,
+    );
+
+    setup_account_menu(
+        controls.tools_menu.clone(),
+        profiles.clone(),
+        profiles_path.clone(),
+        profile_choice.clone(),
+        font,
+    );
+
+    setup_tools_menu(
+        controls.tools_menu,
+        controls.version_choice.clone(),
+        controls.versions,
+        manifest.clone(),
+        controls.java_choice.clone(),
+        java_installations.clone(),
         font,
     );
 
     setup_play_button_callback(
-        play_button,
+        controls.play_button,
+        controls.cancel_button,
         profiles.clone(),
-        version_choice,
+        profiles_path.clone(),
+        manifest.clone(),
+        controls.version_choice,
         profile_choice,
-        java_choice,
-        status_label,
-        progress_bar,
+        controls.java_choice,
+        controls.status_label,
+        controls.progress_bar,
         java_installations,
         error_message.clone(),
         font,
@@ -1,15 +1,29 @@
+use md5::{Digest, Md5};
 use serde::{Deserialize, Serialize};
-use std::{cmp::min, collections::HashMap};
+use std::{cmp::min, collections::HashMap, time::Duration};
 
 #[derive(Debug, Deserialize)]
 pub struct VersionManifest {
+    #[serde(default)]
+    pub latest: Option<LatestVersions>,
     pub versions: Vec<VersionInfo>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LatestVersions {
+    pub release: String,
+    #[allow(dead_code)]
+    pub snapshot: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AssetIndex {
     pub id: String,
     pub url: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,29 +42,131 @@ impl JavaVersion {
 
 #[derive(Debug, Deserialize)]
 pub struct VersionData {
-    pub downloads: Downloads,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, rename = "inheritsFrom")]
+    pub inherits_from: Option<String>,
+    #[serde(default)]
+    pub jar: Option<String>,
+    #[serde(default)]
+    pub downloads: Option<Downloads>,
     #[serde(default)]
     pub libraries: Vec<Library>,
     #[serde(default, rename = "mainClass")]
     pub main_class: String,
-    #[serde(rename = "assetIndex")]
-    pub asset_index: AssetIndex,
+    #[serde(default, rename = "assetIndex")]
+    pub asset_index: Option<AssetIndex>,
     #[serde(default, rename = "javaVersion")]
     pub java_version: Option<JavaVersion>,
+    #[serde(default)]
+    pub arguments: Option<Arguments>,
+    #[serde(default, rename = "minecraftArguments")]
+    pub minecraft_arguments: Option<String>,
+    #[serde(default, rename = "type")]
+    pub version_type: Option<String>,
+    #[serde(default)]
+    pub logging: Option<Logging>,
 }
 
 impl VersionData {
     pub fn get_required_java_version(&self) -> Option<u32> {
         self.java_version.as_ref().map(|jv| jv.get_major_version())
     }
+
+    pub fn jar_id(&self) -> &str {
+        self.jar.as_deref().unwrap_or(&self.id)
+    }
+
+    pub fn merge_parent(self, parent: VersionData) -> VersionData {
+        let mut libraries = self.libraries;
+        for library in parent.libraries {
+            let overridden = library.maven_key().is_some_and(|key| {
+                libraries
+                    .iter()
+                    .any(|existing| existing.maven_key().as_deref() == Some(key.as_str()))
+            });
+            if !overridden {
+                libraries.push(library);
+            }
+        }
+
+        let arguments = match (parent.arguments, self.arguments) {
+            (Some(mut parent_args), Some(child_args)) => {
+                parent_args.game.extend(child_args.game);
+                parent_args.jvm.extend(child_args.jvm);
+                Some(parent_args)
+            }
+            (parent_args, child_args) => child_args.or(parent_args),
+        };
+
+        let jar = self.jar.or(parent.jar).or(Some(parent.id));
+
+        VersionData {
+            id: self.id,
+            inherits_from: parent.inherits_from,
+            jar,
+            downloads: self.downloads.or(parent.downloads),
+            libraries,
+            main_class: if self.main_class.is_empty() {
+                parent.main_class
+            } else {
+                self.main_class
+            },
+            asset_index: self.asset_index.or(parent.asset_index),
+            java_version: self.java_version.or(parent.java_version),
+            arguments,
+            minecraft_arguments: self.minecraft_arguments.or(parent.minecraft_arguments),
+            version_type: self.version_type.or(parent.version_type),
+            logging: self.logging.or(parent.logging),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Arguments {
+    #[serde(default)]
+    pub game: Vec<Argument>,
+    #[serde(default)]
+    pub jvm: Vec<Argument>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Argument {
+    Plain(String),
+    Conditional {
+        #[serde(default)]
+        rules: Vec<Rule>,
+        value: ArgumentValue,
+    },
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ArgumentValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl ArgumentValue {
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            ArgumentValue::Single(value) => vec![value.as_str()],
+            ArgumentValue::Multiple(values) => values.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct VersionInfo {
     pub id: String,
     #[serde(default, rename = "type")]
     pub _type: String,
     pub url: String,
+    #[serde(default, rename = "releaseTime")]
+    pub release_time: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,6 +174,31 @@ pub struct Downloads {
     pub client: DownloadInfo,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct Logging {
+    #[serde(default)]
+    pub client: Option<LoggingConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoggingConfig {
+    pub argument: String,
+    pub file: LoggingFile,
+    #[allow(dead_code)]
+    #[serde(default, rename = "type")]
+    pub config_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoggingFile {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AssetIndexData {
     pub objects: HashMap<String, AssetObject>,
@@ -66,15 +207,29 @@ pub struct AssetIndexData {
 #[derive(Debug, Deserialize)]
 pub struct AssetObject {
     pub hash: String,
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct DownloadInfo {
     pub url: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Library {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
     #[serde(default)]
     pub downloads: Option<LibraryDownloads>,
     #[serde(default)]
@@ -85,6 +240,69 @@ pub struct Library {
     pub extract: Option<Extract>,
 }
 
+impl Library {
+    pub fn artifact(&self) -> Option<Artifact> {
+        if let Some(artifact) = self.downloads.as_ref().and_then(|d| d.artifact.as_ref()) {
+            return Some(artifact.clone());
+        }
+
+        if self.natives.is_some() {
+            return None;
+        }
+
+        let path = maven_path(self.name.as_deref()?)?;
+        let base_url = self.url.as_deref().unwrap_or(DEFAULT_LIBRARY_REPOSITORY);
+        Some(Artifact {
+            url: format!("{}/{}", base_url.trim_end_matches('/'), path),
+            path,
+            sha1: self.sha1.clone(),
+            size: self.size,
+        })
+    }
+
+    pub fn maven_key(&self) -> Option<String> {
+        let name = self.name.as_deref()?;
+        let name = name.split('@').next().unwrap_or(name);
+        let parts: Vec<&str> = name.split(':').collect();
+        match parts.as_slice() {
+            [group, artifact, _version] => Some(format!("{}:{}", group, artifact)),
+            [group, artifact, _version, classifier] => {
+                Some(format!("{}:{}:{}", group, artifact, classifier))
+            }
+            _ => None,
+        }
+    }
+}
+
+const DEFAULT_LIBRARY_REPOSITORY: &str = "https://libraries.minecraft.net/";
+
+pub fn maven_path(name: &str) -> Option<String> {
+    let (coordinates, extension) = match name.split_once('@') {
+        Some((coordinates, extension)) => (coordinates, extension),
+        None => (name, "jar"),
+    };
+
+    let parts: Vec<&str> = coordinates.split(':').collect();
+    let (group, artifact, version, classifier) = match parts.as_slice() {
+        [group, artifact, version] => (*group, *artifact, *version, None),
+        [group, artifact, version, classifier] => (*group, *artifact, *version, Some(*classifier)),
+        _ => return None,
+    };
+
+    let file_name = match classifier {
+        Some(classifier) => format!("{}-{}-{}.{}", artifact, version, classifier, extension),
+        None => format!("{}-{}.{}", artifact, version, extension),
+    };
+
+    Some(format!(
+        "{}/{}/{}/{}",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        file_name
+    ))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LibraryDownloads {
     pub artifact: Option<Artifact>,
@@ -92,24 +310,32 @@ pub struct LibraryDownloads {
     pub classifiers: Option<HashMap<String, Artifact>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct Artifact {
     pub path: String,
     pub url: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Rule {
     pub action: String,
     pub os: Option<Os>,
+    #[serde(default)]
+    pub features: Option<HashMap<String, bool>>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Os {
     pub name: Option<String>,
+    #[serde(default)]
+    pub arch: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Extract {
     pub exclude: Option<Vec<String>>,
 }
@@ -119,10 +345,153 @@ pub struct Profile {
     pub username: String,
     #[serde(default)]
     pub jvm_args: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ProfileStats {
+    #[serde(default)]
+    pub play_time_secs: u64,
+    #[serde(default)]
+    pub sessions: Vec<PlaySession>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlaySession {
+    pub version: String,
+    pub started_at: u64,
+    pub ended_at: u64,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+}
+
+impl Profile {
+    pub fn offline_uuid(&self) -> String {
+        match &self.uuid {
+            Some(uuid) if !uuid.trim().is_empty() => uuid.trim().to_string(),
+            _ => offline_uuid(&self.username),
+        }
+    }
+}
+
+pub fn parse_uuid(value: &str) -> Result<Option<String>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let hex: String = value.chars().filter(|c| *c != '-').collect();
+    let dashes_valid = !value.contains('-') || value.split('-').map(str::len).eq([8, 4, 4, 4, 12]);
+    if hex.len() != 32 || !dashes_valid || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not a valid UUID", value));
+    }
+
+    let hex = hex.to_ascii_lowercase();
+    Ok(Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )))
+}
+
+pub fn offline_uuid(username: &str) -> String {
+    let mut hash: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", username)).into();
+    hash[6] = (hash[6] & 0x0f) | 0x30;
+    hash[8] = (hash[8] & 0x3f) | 0x80;
+
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Account {
+    Microsoft(MicrosoftAccount),
+    Yggdrasil(YggdrasilAccount),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MicrosoftAccount {
+    pub uuid: String,
+    pub name: String,
+    pub access_token: String,
+    pub expires_at: u64,
+    pub refresh_token: String,
+    #[serde(default)]
+    pub xuid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct YggdrasilAccount {
+    pub server_url: String,
+    pub uuid: String,
+    pub name: String,
+    pub access_token: String,
+    pub client_token: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct LaunchSession {
+    pub username: String,
+    pub uuid: String,
+    pub access_token: String,
+    pub user_type: String,
+    pub xuid: Option<String>,
+    pub auth_server: Option<String>,
+}
+
+impl LaunchSession {
+    pub fn offline(profile: &Profile) -> Self {
+        LaunchSession {
+            username: profile.username.clone(),
+            uuid: profile.offline_uuid(),
+            access_token: "0".to_string(),
+            user_type: "legacy".to_string(),
+            xuid: None,
+            auth_server: None,
+        }
+    }
+
+    pub fn microsoft(account: &MicrosoftAccount) -> Self {
+        LaunchSession {
+            username: account.name.clone(),
+            uuid: account.uuid.clone(),
+            access_token: account.access_token.clone(),
+            user_type: "msa".to_string(),
+            xuid: account.xuid.clone(),
+            auth_server: None,
+        }
+    }
+
+    pub fn yggdrasil(account: &YggdrasilAccount) -> Self {
+        LaunchSession {
+            username: account.name.clone(),
+            uuid: account.uuid.clone(),
+            access_token: account.access_token.clone(),
+            user_type: "mojang".to_string(),
+            xuid: None,
+            auth_server: Some(account.server_url.clone()),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub enum LaunchStage {
+    Verifying,
     PreparingLibraries,
     DownloadingLibraries,
     ExtractingNatives,
@@ -143,6 +512,7 @@ pub struct LaunchProgress {
     pub message: String,
     pub current: usize,
     pub total: usize,
+    pub bytes: Option<ByteProgress>,
 }
 
 impl LaunchProgress {
@@ -150,6 +520,7 @@ impl LaunchProgress {
         let current = min(self.current, self.total);
 
         let base_percent = match self.stage {
+            LaunchStage::Verifying => 0.0,
             LaunchStage::PreparingLibraries => 0.0,
             LaunchStage::DownloadingLibraries => 10.0,
             LaunchStage::ExtractingNatives => 20.0,
@@ -169,6 +540,7 @@ impl LaunchProgress {
             LaunchStage::DownloadingAssets => 50.0,
 
             _ => match self.stage {
+                LaunchStage::Verifying => 100.0,
                 LaunchStage::PreparingLibraries => 10.0,
                 LaunchStage::ExtractingNatives => 30.0,
                 LaunchStage::PreparingAssets => 40.0,
@@ -184,8 +556,215 @@ impl LaunchProgress {
         };
 
         let stage_range = next_stage_percent - base_percent;
-        let stage_progress = (current as f64 / self.total as f64) * stage_range;
+        let stage_fraction = match &self.bytes {
+            Some(bytes) if bytes.total > 0 => bytes.fraction(),
+            _ => current as f64 / self.total as f64,
+        };
+        let stage_progress = stage_fraction * stage_range;
 
         (base_percent + stage_progress).min(100.0)
     }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByteProgress {
+    pub downloaded: u64,
+    pub total: u64,
+    pub bytes_per_sec: f64,
+}
+
+impl ByteProgress {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.downloaded as f64 / self.total as f64).min(1.0)
+    }
+
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total.saturating_sub(self.downloaded);
+        (self.bytes_per_sec > 0.0 && remaining > 0)
+            .then(|| Duration::from_secs_f64(remaining as f64 / self.bytes_per_sec))
+    }
+
+    pub fn format(&self) -> String {
+        const MB: f64 = 1024.0 * 1024.0;
+
+        let mut text = format!(
+            "{:.1} / {:.1} MB, {:.1} MB/s",
+            self.downloaded as f64 / MB,
+            self.total as f64 / MB,
+            self.bytes_per_sec / MB
+        );
+        if let Some(eta) = self.eta() {
+            let secs = eta.as_secs();
+            text.push_str(&format!(", ETA {}:{:02}", secs / 60, secs % 60));
+        }
+        text
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(json: &str) -> VersionData {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn library_names(version_data: &VersionData) -> Vec<&str> {
+        version_data
+            .libraries
+            .iter()
+            .filter_map(|library| library.name.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn merge_parent_fills_missing_fields_from_parent() {
+        let child = version(
+            r#"{ "id": "fabric-loader-1.20.1", "inheritsFrom": "1.20.1", "mainClass": "net.fabricmc.loader.Main" }"#,
+        );
+        let parent = version(
+            r#"{
+                "id": "1.20.1",
+                "mainClass": "net.minecraft.client.main.Main",
+                "type": "release",
+                "assetIndex": { "id": "5", "url": "https://example.com/5.json" },
+                "javaVersion": { "component": "java-runtime-gamma", "majorVersion": 17 }
+            }"#,
+        );
+
+        let merged = child.merge_parent(parent);
+
+        assert_eq!(merged.id, "fabric-loader-1.20.1");
+        assert_eq!(merged.main_class, "net.fabricmc.loader.Main");
+        assert_eq!(merged.jar_id(), "1.20.1");
+        assert_eq!(merged.inherits_from, None);
+        assert_eq!(merged.version_type.as_deref(), Some("release"));
+        assert_eq!(merged.get_required_java_version(), Some(17));
+        assert_eq!(
+            merged.asset_index.map(|index| index.id).as_deref(),
+            Some("5")
+        );
+    }
+
+    #[test]
+    fn merge_parent_keeps_child_libraries_first_and_drops_overridden() {
+        let child = version(
+            r#"{
+                "id": "child",
+                "inheritsFrom": "parent",
+                "libraries": [{ "name": "org.ow2.asm:asm:9.6" }, { "name": "net.fabricmc:fabric-loader:0.15.0" }]
+            }"#,
+        );
+        let parent = version(
+            r#"{
+                "id": "parent",
+                "libraries": [{ "name": "org.ow2.asm:asm:9.3" }, { "name": "com.mojang:brigadier:1.1.8" }]
+            }"#,
+        );
+
+        let merged = child.merge_parent(parent);
+
+        assert_eq!(
+            library_names(&merged),
+            [
+                "org.ow2.asm:asm:9.6",
+                "net.fabricmc:fabric-loader:0.15.0",
+                "com.mojang:brigadier:1.1.8"
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_parent_appends_child_arguments() {
+        let child = version(
+            r#"{ "id": "child", "arguments": { "game": ["--child"], "jvm": ["-Dchild=true"] } }"#,
+        );
+        let parent = version(
+            r#"{ "id": "parent", "arguments": { "game": ["--parent"], "jvm": ["-Dparent=true"] } }"#,
+        );
+
+        let arguments = child.merge_parent(parent).arguments.unwrap();
+
+        let plain = |arguments: &[Argument]| -> Vec<String> {
+            arguments
+                .iter()
+                .map(|argument| match argument {
+                    Argument::Plain(value) => value.clone(),
+                    Argument::Conditional { .. } => panic!("unexpected conditional argument"),
+                })
+                .collect()
+        };
+        assert_eq!(plain(&arguments.game), ["--parent", "--child"]);
+        assert_eq!(plain(&arguments.jvm), ["-Dparent=true", "-Dchild=true"]);
+    }
+
+    #[test]
+    fn merge_parent_keeps_explicit_child_jar() {
+        let child = version(r#"{ "id": "child", "jar": "custom" }"#);
+        let parent = version(r#"{ "id": "parent", "jar": "other" }"#);
+
+        assert_eq!(child.merge_parent(parent).jar_id(), "custom");
+    }
+
+    #[test]
+    fn offline_uuid_matches_vanilla_server() {
+        assert_eq!(
+            offline_uuid("Notch"),
+            "b50ad385-829d-3141-a216-7e7d7539ba7f"
+        );
+        assert_eq!(
+            offline_uuid("Steve"),
+            "5627dd98-e6be-3c21-b8a8-e92344183641"
+        );
+    }
+
+    #[test]
+    fn offline_uuid_is_version_3() {
+        let uuid = offline_uuid("Alex");
+        assert_eq!(&uuid[14..15], "3");
+        assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"));
+    }
+
+    #[test]
+    fn profile_prefers_custom_uuid() {
+        let mut profile = Profile {
+            username: "Notch".to_string(),
+            jvm_args: None,
+            uuid: Some(" 069a79f4-44e9-4726-a5be-fca90e38aaf5 ".to_string()),
+            account: None,
+        };
+        assert_eq!(
+            profile.offline_uuid(),
+            "069a79f4-44e9-4726-a5be-fca90e38aaf5"
+        );
+
+        profile.uuid = Some(String::new());
+        assert_eq!(
+            profile.offline_uuid(),
+            "b50ad385-829d-3141-a216-7e7d7539ba7f"
+        );
+    }
+
+    #[test]
+    fn parse_uuid_normalizes_input() {
+        assert_eq!(
+            parse_uuid("069A79F444E94726A5BEFCA90E38AAF5"),
+            Ok(Some("069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string()))
+        );
+        assert_eq!(
+            parse_uuid(" 069a79f4-44e9-4726-a5be-fca90e38aaf5 "),
+            Ok(Some("069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string()))
+        );
+        assert_eq!(parse_uuid("  "), Ok(None));
+    }
+
+    #[test]
+    fn parse_uuid_rejects_malformed_input() {
+        assert!(parse_uuid("not-a-uuid").is_err());
+        assert!(parse_uuid("069a79f4-44e94726-a5be-fca90e38aaf5").is_err());
+        assert!(parse_uuid("069a79f444e94726a5befca90e38aaf").is_err());
+        assert!(parse_uuid("g69a79f444e94726a5befca90e38aaf5").is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,228 @@
+use crate::{
+    background_jobs::BackgroundJob, crash_analyzer, error::LaunchError, game_log::read_log_tail,
+    models::PlaySession, profiles::record_session,
+};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{Child, ExitStatus},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const STATS_FILE: &str = "play_stats.json";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct GameProcess {
+    version_id: String,
+    started_at: SystemTime,
+    log_path: PathBuf,
+    child: Child,
+    background_download: Option<Arc<BackgroundJob>>,
+}
+
+impl GameProcess {
+    pub(crate) fn new(
+        version_id: &str,
+        started_at: SystemTime,
+        log_path: &Path,
+        child: Child,
+        background_download: Option<Arc<BackgroundJob>>,
+    ) -> Self {
+        GameProcess {
+            version_id: version_id.to_string(),
+            started_at,
+            log_path: log_path.to_path_buf(),
+            child,
+            background_download,
+        }
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
+    pub fn background_download(&self) -> Option<&Arc<BackgroundJob>> {
+        self.background_download.as_ref()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InstanceInfo {
+    pub pid: u32,
+    pub profile: String,
+    pub version: String,
+    pub started_at: SystemTime,
+    pub log_path: PathBuf,
+}
+
+struct Instance {
+    profile: String,
+    process: GameProcess,
+    killed: bool,
+}
+
+type CrashHandler = Arc<dyn Fn(LaunchError) + Send + Sync>;
+
+pub struct ProcessRegistry {
+    game_dir: PathBuf,
+    stats_path: PathBuf,
+    instances: Mutex<Vec<Instance>>,
+    finishing: AtomicUsize,
+    crash_handler: Mutex<Option<CrashHandler>>,
+}
+
+impl ProcessRegistry {
+    pub fn new(game_dir: &Path) -> Arc<Self> {
+        Arc::new(ProcessRegistry {
+            game_dir: game_dir.to_path_buf(),
+            stats_path: game_dir.join(STATS_FILE),
+            instances: Mutex::new(Vec::new()),
+            finishing: AtomicUsize::new(0),
+            crash_handler: Mutex::new(None),
+        })
+    }
+
+    pub fn on_crash(&self, handler: impl Fn(LaunchError) + Send + Sync + 'static) {
+        *self.crash_handler.lock().unwrap() = Some(Arc::new(handler));
+    }
+
+    pub fn stats_path(&self) -> &Path {
+        &self.stats_path
+    }
+
+    pub fn register(self: &Arc<Self>, profile: &str, process: GameProcess) -> u32 {
+        let pid = process.pid();
+        self.instances.lock().unwrap().push(Instance {
+            profile: profile.to_string(),
+            process,
+            killed: false,
+        });
+
+        let registry = self.clone();
+        thread::spawn(move || {
+            thread::sleep(POLL_INTERVAL);
+            while registry.poll(pid) {
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+
+        pid
+    }
+
+    pub fn running(&self) -> Vec<InstanceInfo> {
+        self.instances
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|instance| InstanceInfo {
+                pid: instance.process.pid(),
+                profile: instance.profile.clone(),
+                version: instance.process.version_id.clone(),
+                started_at: instance.process.started_at,
+                log_path: instance.process.log_path.clone(),
+            })
+            .collect()
+    }
+
+    pub fn kill(&self, pid: u32) -> io::Result<()> {
+        let mut instances = self.instances.lock().unwrap();
+        let instance = instances
+            .iter_mut()
+            .find(|instance| instance.process.pid() == pid)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, "Game instance is not running")
+            })?;
+
+        instance.killed = true;
+        instance.process.child.kill()
+    }
+
+    pub fn wait_all(&self) {
+        while !self.instances.lock().unwrap().is_empty()
+            || self.finishing.load(Ordering::SeqCst) > 0
+        {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn poll(&self, pid: u32) -> bool {
+        let mut instances = self.instances.lock().unwrap();
+        let Some(index) = instances
+            .iter()
+            .position(|instance| instance.process.pid() == pid)
+        else {
+            return false;
+        };
+
+        let status = match instances[index].process.child.try_wait() {
+            Ok(None) => return true,
+            Ok(Some(status)) => Some(status),
+            Err(_) => None,
+        };
+
+        let instance = instances.remove(index);
+        self.finishing.fetch_add(1, Ordering::SeqCst);
+        drop(instances);
+
+        self.finish(instance, status);
+        self.finishing.fetch_sub(1, Ordering::SeqCst);
+        false
+    }
+
+    fn finish(&self, instance: Instance, status: Option<ExitStatus>) {
+        if let Some(status) = status.filter(|status| !status.success() && !instance.killed) {
+            let handler = self.crash_handler.lock().unwrap().clone();
+            if let Some(handler) = handler {
+                let log_tail = read_log_tail(&instance.process.log_path);
+                let crash = Box::new(crash_analyzer::analyze(
+                    &self.game_dir,
+                    instance.process.started_at,
+                    &log_tail,
+                ));
+                handler(LaunchError::ProcessExited {
+                    code: status.code().unwrap_or(-1),
+                    log_tail,
+                    crash,
+                });
+            }
+        }
+
+        let _ = record_session(
+            &self.stats_path,
+            &instance.profile,
+            PlaySession {
+                version: instance.process.version_id,
+                started_at: unix_secs(instance.process.started_at),
+                ended_at: unix_secs(SystemTime::now()),
+                exit_code: status.and_then(|status| status.code()),
+            },
+        );
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn format_duration(secs: u64) -> String {
+    match (secs / 3600, secs / 60 % 60) {
+        (0, 0) => format!("{}s", secs),
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
\ No newline at end of file
@@ -1,11 +1,18 @@
-use crate::models::Profile;
+use crate::{
+    auth::{AuthEndpoints, MicrosoftAuth},
+    models::{Account, LaunchSession, PlaySession, Profile, ProfileStats},
+    yggdrasil::YggdrasilAuth,
+};
 use std::{
+    collections::HashMap,
+    error::Error,
     fs::{self, File},
-    io::{BufReader, BufWriter},
+    io::{BufReader, BufWriter, Write},
     path::Path,
+    sync::Mutex,
 };
 
-pub fn read_profiles(path: &Path) -> Result<Vec<Profile>, Box<dyn std::error::Error>> {
+pub fn read_profiles(path: &Path) -> Result<Vec<Profile>, Box<dyn Error>> {
     if path.exists() {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
@@ -16,7 +23,7 @@ pub fn read_profiles(path: &Path) -> Result<Vec<Profile>, Box<dyn std::error::Er
     }
 }
 
-pub fn write_profiles(path: &Path, profiles: &[Profile]) -> Result<(), Box<dyn std::error::Error>> {
+pub fn write_profiles(path: &Path, profiles: &[Profile]) -> Result<(), Box<dyn Error>> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -25,4 +32,62 @@ pub fn write_profiles(path: &Path, profiles: &[Profile]) -> Result<(), Box<dyn s
     let writer = BufWriter::new(file);
     serde_json::to_writer_pretty(writer, profiles)?;
     Ok(())
+}
+
+pub fn refresh_account(account: &Account) -> Result<(Account, LaunchSession), Box<dyn Error>> {
+    match account {
+        Account::Microsoft(account) => {
+            let refreshed = MicrosoftAuth::new(AuthEndpoints::new()).ensure_valid(account)?;
+            let session = LaunchSession::microsoft(&refreshed);
+            Ok((Account::Microsoft(refreshed), session))
+        }
+        Account::Yggdrasil(account) => {
+            let refreshed = YggdrasilAuth::new(&account.server_url).ensure_valid(account)?;
+            let session = LaunchSession::yggdrasil(&refreshed);
+            Ok((Account::Yggdrasil(refreshed), session))
+        }
+    }
+}
+
+const MAX_RECORDED_SESSIONS: usize = 50;
+
+static STATS_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn read_stats(path: &Path) -> Result<HashMap<String, ProfileStats>, Box<dyn Error>> {
+    if path.exists() {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        Ok(serde_json::from_reader(reader)?)
+    } else {
+        Ok(HashMap::new())
+    }
+}
+
+pub fn record_session(
+    path: &Path,
+    profile: &str,
+    session: PlaySession,
+) -> Result<(), Box<dyn Error>> {
+    let _guard = STATS_LOCK.lock().unwrap();
+    let mut stats = read_stats(path)?;
+    let profile_stats = stats.entry(profile.to_string()).or_default();
+
+    profile_stats.play_time_secs += session.ended_at.saturating_sub(session.started_at);
+    profile_stats.sessions.push(session);
+    if profile_stats.sessions.len() > MAX_RECORDED_SESSIONS {
+        let excess = profile_stats.sessions.len() - MAX_RECORDED_SESSIONS;
+        profile_stats.sessions.drain(..excess);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = path.with_extension("json.tmp");
+    let mut writer = BufWriter::new(File::create(&temp_path)?);
+    serde_json::to_writer_pretty(&mut writer, &stats)?;
+    writer.flush()?;
+    drop(writer);
+    fs::rename(&temp_path, path)?;
+    Ok(())
 }
\ No newline at end of file
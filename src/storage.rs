@@ -0,0 +1,656 @@
+use crate::{
+    downloader::{asset_index_job, asset_object_jobs, library_jobs},
+    models::AssetIndexData,
+    version_manager::{load_local_version, local_version_path, resolve_local_version},
+    yggdrasil::AUTHLIB_INJECTOR_DIR,
+};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const LOADER_LIBRARY_GROUPS: &[&str] = &["net.minecraftforge:", "net.neoforged:"];
+const LOADER_LIBRARY_DIRS: &[&str] = &[
+    "net/minecraftforge",
+    "net/neoforged",
+    "net/minecraft",
+    "de/oceanlabs/mcp",
+];
+const LOG_DIRS: &[&str] = &["logs", "crash-reports"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Category {
+    Versions,
+    Libraries,
+    AssetObjects,
+    AssetIndexes,
+    Logs,
+    Other,
+}
+
+impl Category {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Versions => "Versions",
+            Category::Libraries => "Libraries",
+            Category::AssetObjects => "Asset objects",
+            Category::AssetIndexes => "Asset indexes",
+            Category::Logs => "Logs",
+            Category::Other => "Other",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DiskUsage {
+    pub versions: Vec<(String, u64)>,
+    pub categories: Vec<(Category, u64)>,
+}
+
+impl DiskUsage {
+    pub fn total(&self) -> u64 {
+        self.categories.iter().map(|(_, bytes)| bytes).sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UnusedFile {
+    pub category: Category,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GcPlan {
+    pub files: Vec<UnusedFile>,
+    pub warnings: Vec<String>,
+}
+
+impl GcPlan {
+    pub fn bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.bytes).sum()
+    }
+
+    pub fn count(&self, category: Category) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.category == category)
+            .count()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GcResult {
+    pub removed: usize,
+    pub bytes: u64,
+    pub skipped: usize,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+#[derive(Default)]
+struct References {
+    version_dirs: HashSet<PathBuf>,
+    orphan_version_dirs: Vec<PathBuf>,
+    libraries: HashSet<PathBuf>,
+    library_dirs: Vec<PathBuf>,
+    indexes: HashSet<PathBuf>,
+    objects: HashSet<PathBuf>,
+    versions_known: bool,
+    objects_known: bool,
+    warnings: Vec<String>,
+}
+
+pub fn disk_usage(game_dir: &Path, libraries_dir: &Path) -> DiskUsage {
+    let mut usage = DiskUsage::default();
+    let assets_dir = game_dir.join("assets");
+
+    for path in read_dir_paths(&game_dir.join("versions")) {
+        if path.is_dir() {
+            let id = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            usage.versions.push((id, dir_size(&path)));
+        }
+    }
+    usage.versions.sort();
+
+    let mut categorized: Vec<PathBuf> = vec![
+        game_dir.join("versions"),
+        libraries_dir.to_path_buf(),
+        assets_dir.join("objects"),
+        assets_dir.join("indexes"),
+    ];
+    categorized.extend(LOG_DIRS.iter().map(|dir| game_dir.join(dir)));
+
+    let other = read_dir_paths(game_dir)
+        .into_iter()
+        .chain(read_dir_paths(&assets_dir))
+        .filter(|path| *path != assets_dir && !categorized.contains(path))
+        .map(|path| dir_size(&path))
+        .sum();
+
+    usage.categories = vec![
+        (
+            Category::Versions,
+            usage.versions.iter().map(|(_, bytes)| bytes).sum(),
+        ),
+        (Category::Libraries, dir_size(libraries_dir)),
+        (
+            Category::AssetObjects,
+            dir_size(&assets_dir.join("objects")),
+        ),
+        (
+            Category::AssetIndexes,
+            dir_size(&assets_dir.join("indexes")),
+        ),
+        (
+            Category::Logs,
+            LOG_DIRS
+                .iter()
+                .map(|dir| dir_size(&game_dir.join(dir)))
+                .sum(),
+        ),
+        (Category::Other, other),
+    ];
+
+    usage
+}
+
+pub fn find_unused(game_dir: &Path, libraries_dir: &Path) -> GcPlan {
+    let references = references(game_dir, libraries_dir);
+    GcPlan {
+        files: unused_files(game_dir, libraries_dir, &references),
+        warnings: references.warnings,
+    }
+}
+
+pub fn collect_garbage(game_dir: &Path, libraries_dir: &Path, plan: &GcPlan) -> GcResult {
+    let references = references(game_dir, libraries_dir);
+    let still_unused: HashSet<PathBuf> = unused_files(game_dir, libraries_dir, &references)
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+
+    let mut result = GcResult::default();
+    for file in &plan.files {
+        if !still_unused.contains(&file.path) {
+            result.skipped += 1;
+            continue;
+        }
+
+        let removed = if file.category == Category::Versions {
+            fs::remove_dir_all(&file.path)
+        } else {
+            fs::remove_file(&file.path)
+        };
+        match removed {
+            Ok(()) => {
+                result.removed += 1;
+                result.bytes += file.bytes;
+            }
+            Err(e) => result.failed.push((file.path.clone(), e.to_string())),
+        }
+    }
+
+    remove_empty_dirs(libraries_dir);
+    remove_empty_dirs(&game_dir.join("assets").join("objects"));
+
+    result
+}
+
+pub fn remove_version(game_dir: &Path, version_id: &str) -> Result<(), Box<dyn Error>> {
+    let version_dir = game_dir.join("versions").join(version_id);
+    if !version_dir.is_dir() {
+        return Err(format!("Version {} is not installed", version_id).into());
+    }
+
+    for path in read_dir_paths(&game_dir.join("versions")) {
+        let Some(id) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        if id == version_id || !local_version_path(game_dir, &id).exists() {
+            continue;
+        }
+
+        let check_error = |e: Box<dyn Error>| format!("Cannot check version {}: {}", id, e);
+        let inherits_from = load_local_version(game_dir, &id)
+            .map_err(check_error)?
+            .inherits_from;
+        let version_data = resolve_local_version(game_dir, &id).map_err(check_error)?;
+        if version_data.jar_id() == version_id || inherits_from.as_deref() == Some(version_id) {
+            return Err(format!("Version {} is used by {}", version_id, id).into());
+        }
+    }
+
+    fs::remove_dir_all(version_dir)?;
+    Ok(())
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.2} GB", bytes / GB)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes / MB)
+    } else {
+        format!("{:.0} KB", bytes / KB)
+    }
+}
+
+fn references(game_dir: &Path, libraries_dir: &Path) -> References {
+    let mut references = References {
+        versions_known: true,
+        objects_known: true,
+        library_dirs: vec![libraries_dir.join(AUTHLIB_INJECTOR_DIR)],
+        ..Default::default()
+    };
+    let versions_dir = game_dir.join("versions");
+    let objects_dir = game_dir.join("assets").join("objects");
+
+    let mut resolved = 0;
+    let mut candidate_dirs = Vec::new();
+    for path in read_dir_paths(&versions_dir) {
+        let Some(id) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        if !path.is_dir() {
+            continue;
+        }
+        if !local_version_path(game_dir, &id).exists() {
+            candidate_dirs.push((id, path));
+            continue;
+        }
+
+        references.version_dirs.insert(path.clone());
+        let version_data = match resolve_local_version(game_dir, &id) {
+            Ok(version_data) => version_data,
+            Err(e) => {
+                references.skip_unknown(format!(
+                    "Libraries and assets were not checked because {} could not be read: {}",
+                    id, e
+                ));
+                continue;
+            }
+        };
+        resolved += 1;
+
+        references
+            .version_dirs
+            .insert(versions_dir.join(version_data.jar_id()));
+        references.libraries.extend(
+            library_jobs(&version_data.libraries, libraries_dir)
+                .into_iter()
+                .map(|job| job.dest),
+        );
+
+        let uses_loader_libraries = version_data.libraries.iter().any(|library| {
+            library.name.as_deref().is_some_and(|name| {
+                LOADER_LIBRARY_GROUPS
+                    .iter()
+                    .any(|group| name.starts_with(group))
+            })
+        });
+        if uses_loader_libraries {
+            references.library_dirs.extend(
+                LOADER_LIBRARY_DIRS
+                    .iter()
+                    .map(|dir| libraries_dir.join(dir)),
+            );
+        }
+
+        let Ok(index_job) = asset_index_job(game_dir, &version_data) else {
+            continue;
+        };
+        let asset_index_data = fs::read_to_string(&index_job.dest)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<AssetIndexData>(&content).map_err(|e| e.to_string())
+            });
+        match asset_index_data {
+            Ok(asset_index_data) => references.objects.extend(
+                asset_object_jobs(&asset_index_data, &objects_dir)
+                    .into_iter()
+                    .map(|job| job.dest),
+            ),
+            Err(e) => {
+                references.objects_known = false;
+                references.warnings.push(format!(
+                    "Asset objects were not checked because the asset index of {} could not be read: {}",
+                    id, e
+                ));
+            }
+        }
+        references.indexes.insert(index_job.dest);
+    }
+
+    for (id, path) in candidate_dirs {
+        if references.version_dirs.contains(&path) {
+            continue;
+        }
+        if has_game_files(&path, &id) {
+            references.version_dirs.insert(path);
+            references.skip_unknown(format!(
+                "Libraries and assets were not checked because {} has no version JSON",
+                id
+            ));
+        } else {
+            references.orphan_version_dirs.push(path);
+        }
+    }
+
+    if resolved == 0 {
+        references.skip_unknown(
+            "Libraries and assets were not checked because no installed version could be read"
+                .to_string(),
+        );
+    }
+
+    references
+}
+
+impl References {
+    fn skip_unknown(&mut self, warning: String) {
+        self.versions_known = false;
+        self.objects_known = false;
+        self.warnings.push(warning);
+    }
+}
+
+fn has_game_files(version_dir: &Path, id: &str) -> bool {
+    version_dir.join(format!("{}.jar", id)).exists()
+        || version_dir.join("client.jar").exists()
+        || version_dir.join("natives").is_dir()
+}
+
+fn unused_files(game_dir: &Path, libraries_dir: &Path, references: &References) -> Vec<UnusedFile> {
+    let assets_dir = game_dir.join("assets");
+    let mut unused: Vec<UnusedFile> = references
+        .orphan_version_dirs
+        .iter()
+        .map(|path| UnusedFile {
+            category: Category::Versions,
+            path: path.clone(),
+            bytes: dir_size(path),
+        })
+        .collect();
+
+    if references.versions_known {
+        let mut libraries = Vec::new();
+        collect_files(libraries_dir, &mut libraries);
+        unused.extend(
+            libraries
+                .into_iter()
+                .filter(|path| {
+                    !references.libraries.contains(path)
+                        && !references
+                            .library_dirs
+                            .iter()
+                            .any(|dir| path.starts_with(dir))
+                })
+                .filter_map(|path| unused_file(Category::Libraries, path)),
+        );
+
+        unused.extend(
+            read_dir_paths(&assets_dir.join("indexes"))
+                .into_iter()
+                .filter(|path| path.is_file() && !references.indexes.contains(path))
+                .filter_map(|path| unused_file(Category::AssetIndexes, path)),
+        );
+    }
+
+    if references.objects_known {
+        let mut objects = Vec::new();
+        collect_files(&assets_dir.join("objects"), &mut objects);
+        unused.extend(
+            objects
+                .into_iter()
+                .filter(|path| !references.objects.contains(path))
+                .filter_map(|path| unused_file(Category::AssetObjects, path)),
+        );
+    }
+
+    unused
+}
+
+fn unused_file(category: Category, path: PathBuf) -> Option<UnusedFile> {
+    if path.extension().is_some_and(|ext| ext == "part") {
+        return None;
+    }
+    let bytes = fs::metadata(&path).ok()?.len();
+    Some(UnusedFile {
+        category,
+        path,
+        bytes,
+    })
+}
+
+fn read_dir_paths(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for path in read_dir_paths(dir) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+fn dir_size(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => {
+            read_dir_paths(path).iter().map(|path| dir_size(path)).sum()
+        }
+        Ok(metadata) => metadata.len(),
+        Err(_) => 0,
+    }
+}
+
+fn remove_empty_dirs(dir: &Path) {
+    for path in read_dir_paths(dir) {
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            let _ = fs::remove_dir(&path);
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USED_HASH: &str = "aa00000000000000000000000000000000000000";
+    const UNUSED_HASH: &str = "bb00000000000000000000000000000000000000";
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "minecraft_launcher_storage_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn install_version(game_dir: &Path, libraries_dir: &Path) {
+        write(
+            &local_version_path(game_dir, "1.0"),
+            r#"{
+                "id": "1.0",
+                "libraries": [{ "name": "org.example:used:1.0" }],
+                "assetIndex": { "id": "1.0", "url": "https://example.com/1.0.json" }
+            }"#,
+        );
+        write(
+            &game_dir.join("assets").join("indexes").join("1.0.json"),
+            &format!(
+                r#"{{ "objects": {{ "used": {{ "hash": "{}" }} }} }}"#,
+                USED_HASH
+            ),
+        );
+        write(
+            &libraries_dir.join("org/example/used/1.0/used-1.0.jar"),
+            "jar",
+        );
+    }
+
+    fn add_unused_files(game_dir: &Path, libraries_dir: &Path) {
+        let objects_dir = game_dir.join("assets").join("objects");
+        write(&objects_dir.join("aa").join(USED_HASH), "used");
+        write(&objects_dir.join("bb").join(UNUSED_HASH), "unused");
+        write(
+            &game_dir.join("assets").join("indexes").join("old.json"),
+            "{}",
+        );
+        write(
+            &libraries_dir.join("org/example/unused/1.0/unused-1.0.jar"),
+            "jar",
+        );
+        fs::create_dir_all(game_dir.join("versions").join("leftover")).unwrap();
+    }
+
+    fn unused_paths(plan: &GcPlan) -> HashSet<PathBuf> {
+        plan.files.iter().map(|file| file.path.clone()).collect()
+    }
+
+    #[test]
+    fn lists_only_unreferenced_files() {
+        let game_dir = test_dir("unreferenced");
+        let libraries_dir = game_dir.join("libraries");
+        install_version(&game_dir, &libraries_dir);
+        add_unused_files(&game_dir, &libraries_dir);
+
+        let plan = find_unused(&game_dir, &libraries_dir);
+
+        let expected: HashSet<PathBuf> = [
+            game_dir.join("versions").join("leftover"),
+            libraries_dir.join("org/example/unused/1.0/unused-1.0.jar"),
+            game_dir.join("assets").join("indexes").join("old.json"),
+            game_dir
+                .join("assets")
+                .join("objects")
+                .join("bb")
+                .join(UNUSED_HASH),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(unused_paths(&plan), expected);
+        assert!(plan.warnings.is_empty());
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn unreadable_version_keeps_libraries_and_assets() {
+        let game_dir = test_dir("unreadable");
+        let libraries_dir = game_dir.join("libraries");
+        install_version(&game_dir, &libraries_dir);
+        add_unused_files(&game_dir, &libraries_dir);
+        write(&local_version_path(&game_dir, "modded"), "{ broken");
+
+        let plan = find_unused(&game_dir, &libraries_dir);
+
+        let expected: HashSet<PathBuf> = [game_dir.join("versions").join("leftover")]
+            .into_iter()
+            .collect();
+        assert_eq!(unused_paths(&plan), expected);
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].contains("modded"));
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn version_without_json_is_not_orphaned() {
+        let game_dir = test_dir("jar_only");
+        let libraries_dir = game_dir.join("libraries");
+        install_version(&game_dir, &libraries_dir);
+        add_unused_files(&game_dir, &libraries_dir);
+        write(
+            &game_dir.join("versions").join("custom").join("custom.jar"),
+            "jar",
+        );
+        fs::create_dir_all(
+            game_dir
+                .join("versions")
+                .join("natives_only")
+                .join("natives"),
+        )
+        .unwrap();
+
+        let plan = find_unused(&game_dir, &libraries_dir);
+
+        let expected: HashSet<PathBuf> = [game_dir.join("versions").join("leftover")]
+            .into_iter()
+            .collect();
+        assert_eq!(unused_paths(&plan), expected);
+        assert_eq!(plan.warnings.len(), 2);
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn no_installed_versions_lists_nothing() {
+        let game_dir = test_dir("empty");
+        let libraries_dir = game_dir.join("libraries");
+        add_unused_files(&game_dir, &libraries_dir);
+        fs::remove_dir_all(game_dir.join("versions")).unwrap();
+
+        let plan = find_unused(&game_dir, &libraries_dir);
+
+        assert!(plan.files.is_empty());
+        assert_eq!(plan.warnings.len(), 1);
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+
+    #[test]
+    fn collect_garbage_skips_files_that_became_used() {
+        let game_dir = test_dir("collect");
+        let libraries_dir = game_dir.join("libraries");
+        install_version(&game_dir, &libraries_dir);
+        add_unused_files(&game_dir, &libraries_dir);
+        let plan = find_unused(&game_dir, &libraries_dir);
+        write(&local_version_path(&game_dir, "leftover"), "{}");
+
+        let result = collect_garbage(&game_dir, &libraries_dir, &plan);
+
+        assert_eq!(result.removed, 3);
+        assert_eq!(result.skipped, 1);
+        assert!(result.failed.is_empty());
+        assert!(game_dir.join("versions").join("leftover").exists());
+        assert!(
+            libraries_dir
+                .join("org/example/used/1.0/used-1.0.jar")
+                .exists()
+        );
+
+        let _ = fs::remove_dir_all(game_dir);
+    }
+}
\ No newline at end of file
@@ -1,41 +1,311 @@
-use crate::models::{VersionData, VersionManifest};
-use std::error::Error;
+use crate::{
+    download_engine::DownloadEngine,
+    downloader::is_file_valid,
+    models::{VersionData, VersionInfo, VersionManifest},
+};
+use reqwest::{
+    StatusCode,
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-pub fn fetch_version_manifest(manifest_url: &str) -> Result<VersionManifest, Box<dyn Error>> {
-    let response = reqwest::blocking::get(manifest_url)?;
-    let manifest_data: VersionManifest = response.json()?;
-    Ok(manifest_data)
+const MAX_INHERITANCE_DEPTH: usize = 8;
+const VERSION_MANIFEST_URL: &str =
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+#[derive(Default, Serialize, Deserialize)]
+struct ManifestCacheInfo {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+}
+
+pub struct ManifestService {
+    manifest_url: String,
+    cache_dir: PathBuf,
+    client: reqwest::blocking::Client,
+    manifest: Mutex<Option<Arc<VersionManifest>>>,
 }
 
-pub fn fetch_version_data(version_url: &str) -> Result<VersionData, Box<dyn Error>> {
-    let response = reqwest::blocking::get(version_url)?;
-    let version_data: VersionData = response.json()?;
+impl ManifestService {
+    pub fn new(game_dir: &Path) -> Self {
+        let manifest_url = std::env::var("VERSION_MANIFEST_URL")
+            .unwrap_or_else(|_| VERSION_MANIFEST_URL.to_string());
+        Self::with_manifest_url(game_dir, &manifest_url)
+    }
+
+    pub fn with_manifest_url(game_dir: &Path, manifest_url: &str) -> Self {
+        ManifestService {
+            manifest_url: manifest_url.to_string(),
+            cache_dir: game_dir.join("versions"),
+            client: DownloadEngine::shared().client().clone(),
+            manifest: Mutex::new(None),
+        }
+    }
+
+    pub fn with_client(self, client: reqwest::blocking::Client) -> Self {
+        ManifestService { client, ..self }
+    }
+
+    pub fn manifest(&self) -> Result<Arc<VersionManifest>, Box<dyn Error>> {
+        let mut manifest = self.manifest.lock().unwrap();
+        if let Some(manifest) = manifest.as_ref() {
+            return Ok(manifest.clone());
+        }
+
+        let loaded = Arc::new(self.load()?);
+        *manifest = Some(loaded.clone());
+        Ok(loaded)
+    }
+
+    pub fn find(&self, version_id: &str) -> Option<VersionInfo> {
+        self.manifest()
+            .ok()?
+            .versions
+            .iter()
+            .find(|version| version.id == version_id)
+            .cloned()
+    }
+
+    pub fn latest_release(&self) -> Option<String> {
+        self.manifest()
+            .ok()?
+            .latest
+            .as_ref()
+            .map(|latest| latest.release.clone())
+    }
+
+    pub fn fetch_version_data(&self, version_url: &str) -> Result<String, Box<dyn Error>> {
+        let response = self.client.get(version_url).send()?.error_for_status()?;
+        Ok(response.text()?)
+    }
+
+    fn cache_path(&self) -> PathBuf {
+        self.cache_dir.join("version_manifest_v2.json")
+    }
+
+    fn cache_info_path(&self) -> PathBuf {
+        self.cache_dir.join("version_manifest_v2.cache.json")
+    }
+
+    fn load(&self) -> Result<VersionManifest, Box<dyn Error>> {
+        let cached = fs::read_to_string(self.cache_path()).ok();
+
+        match self.revalidate(cached.is_some()) {
+            Ok(Some(content)) => Ok(serde_json::from_str(&content)?),
+            Ok(None) => Ok(serde_json::from_str(cached.as_deref().unwrap_or_default())?),
+            Err(e) => match cached {
+                Some(content) => Ok(serde_json::from_str(&content)?),
+                None => Err(e),
+            },
+        }
+    }
+
+    fn revalidate(&self, has_cache: bool) -> Result<Option<String>, Box<dyn Error>> {
+        let cache_info: ManifestCacheInfo = fs::read_to_string(self.cache_info_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        let mut request = self.client.get(&self.manifest_url);
+        if has_cache {
+            if let Some(etag) = &cache_info.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cache_info.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send()?;
+        if has_cache && response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+
+        let response = response.error_for_status()?;
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let cache_info = ManifestCacheInfo {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        let content = response.text()?;
+        serde_json::from_str::<VersionManifest>(&content)?;
+
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(self.cache_path(), &content)?;
+        fs::write(self.cache_info_path(), serde_json::to_string(&cache_info)?)?;
+
+        Ok(Some(content))
+    }
+}
+
+pub fn local_version_path(game_dir: &Path, version_id: &str) -> PathBuf {
+    game_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id))
+}
+
+pub fn load_local_version(
+    game_dir: &Path,
+    version_id: &str,
+) -> Result<VersionData, Box<dyn Error>> {
+    let content = fs::read_to_string(local_version_path(game_dir, version_id))?;
+    let mut version_data: VersionData = serde_json::from_str(&content)?;
+    if version_data.id.is_empty() {
+        version_data.id = version_id.to_string();
+    }
     Ok(version_data)
 }
 
-pub fn get_version_ids() -> String {
-    let mut versions = String::new();
-    match fetch_version_manifest("https://launchermeta.mojang.com/mc/game/version_manifest.json") {
-        Ok(manifest) => {
-            for version in &manifest.versions {
-                versions.push_str(&format!("{}|{}|", version.id, version._type));
+pub fn resolve_local_version(
+    game_dir: &Path,
+    version_id: &str,
+) -> Result<VersionData, Box<dyn Error>> {
+    resolve_local_version_with_depth(game_dir, version_id, 0)
+}
+
+fn resolve_local_version_with_depth(
+    game_dir: &Path,
+    version_id: &str,
+    depth: usize,
+) -> Result<VersionData, Box<dyn Error>> {
+    if depth > MAX_INHERITANCE_DEPTH {
+        return Err(format!("Version inheritance of {} is too deep", version_id).into());
+    }
+
+    let version_data = load_local_version(game_dir, version_id)
+        .map_err(|e| format!("Cannot read version {}: {}", version_id, e))?;
+    match version_data.inherits_from.clone() {
+        Some(parent_id) => {
+            let parent = resolve_local_version_with_depth(game_dir, &parent_id, depth + 1)?;
+            Ok(version_data.merge_parent(parent))
+        }
+        None => Ok(version_data),
+    }
+}
+
+pub fn resolve_version(
+    game_dir: &Path,
+    manifest: &ManifestService,
+    version_id: &str,
+) -> Result<VersionData, Box<dyn Error>> {
+    resolve_version_with_depth(game_dir, manifest, version_id, 0)
+}
+
+fn resolve_version_with_depth(
+    game_dir: &Path,
+    manifest: &ManifestService,
+    version_id: &str,
+    depth: usize,
+) -> Result<VersionData, Box<dyn Error>> {
+    if depth > MAX_INHERITANCE_DEPTH {
+        return Err(format!("Version inheritance of {} is too deep", version_id).into());
+    }
+
+    let version_path = local_version_path(game_dir, version_id);
+    let mut version_data = match manifest.find(version_id) {
+        Some(info) if needs_download(&version_path, info.sha1.as_deref()) => {
+            match download_version(game_dir, manifest, &info) {
+                Ok(version_data) => version_data,
+                Err(_) if version_path.exists() => load_local_version(game_dir, version_id)?,
+                Err(e) => return Err(e),
             }
         }
-        Err(_) => {}
+        _ if version_path.exists() => load_local_version(game_dir, version_id)?,
+        _ => return Err(format!("Version {} was not found", version_id).into()),
+    };
+
+    if version_data.id.is_empty() {
+        version_data.id = version_id.to_string();
+    }
+
+    match version_data.inherits_from.clone() {
+        Some(parent_id) => {
+            let parent = resolve_version_with_depth(game_dir, manifest, &parent_id, depth + 1)?;
+            Ok(version_data.merge_parent(parent))
+        }
+        None => Ok(version_data),
+    }
+}
+
+fn needs_download(version_path: &Path, sha1: Option<&str>) -> bool {
+    !version_path.exists()
+        || sha1.is_some_and(|sha1| !is_file_valid(version_path, Some(sha1), None))
+}
+
+fn download_version(
+    game_dir: &Path,
+    manifest: &ManifestService,
+    info: &VersionInfo,
+) -> Result<VersionData, Box<dyn Error>> {
+    let content = manifest.fetch_version_data(&info.url)?;
+    let version_data: VersionData = serde_json::from_str(&content)?;
+
+    let version_path = local_version_path(game_dir, &info.id);
+    if let Some(parent) = version_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&version_path, content)?;
+
+    Ok(version_data)
+}
+
+pub fn get_local_versions(game_dir: &Path) -> Vec<(String, String)> {
+    let mut versions = Vec::new();
+
+    let entries = match fs::read_dir(game_dir.join("versions")) {
+        Ok(entries) => entries,
+        Err(_) => return versions,
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let version_id = entry.file_name().to_string_lossy().to_string();
+        if let Ok(version_data) = load_local_version(game_dir, &version_id) {
+            let version_type = version_data
+                .version_type
+                .unwrap_or_else(|| "release".to_string());
+            versions.push((version_id, version_type));
+        }
     }
+
+    versions.sort();
     versions
 }
 
-pub fn get_version_link(version_id: String) -> Option<String> {
-    match fetch_version_manifest("https://launchermeta.mojang.com/mc/game/version_manifest.json") {
-        Ok(manifest) => {
-            for version in manifest.versions {
-                if version.id == version_id {
-                    return Some(version.url);
-                }
-            }
-            None
+pub fn get_version_list(game_dir: &Path, manifest: &ManifestService) -> Vec<(String, String)> {
+    let mut versions = Vec::new();
+    let manifest = manifest.manifest();
+
+    for (version_id, version_type) in get_local_versions(game_dir) {
+        let in_manifest = manifest
+            .as_ref()
+            .is_ok_and(|m| m.versions.iter().any(|v| v.id == version_id));
+        if !in_manifest {
+            versions.push((version_id, version_type));
         }
-        Err(_) => None,
     }
+
+    if let Ok(manifest) = manifest {
+        let mut manifest_versions: Vec<&VersionInfo> = manifest.versions.iter().collect();
+        manifest_versions.sort_by(|a, b| b.release_time.cmp(&a.release_time));
+        for version in manifest_versions {
+            versions.push((version.id.clone(), version._type.clone()));
+        }
+    }
+
+    versions
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use fltk::prelude::WindowExt;
 use winapi::{
     shared::windef::HWND,
+    um::wincon::{ATTACH_PARENT_PROCESS, AttachConsole},
     um::winuser::{
     GWL_EXSTYLE, GWL_STYLE, GetWindowLongPtrW, SetActiveWindow, SetWindowLongPtrW, WS_CAPTION,
     WS_EX_APPWINDOW, WS_MAXIMIZEBOX, WS_MINIMIZEBOX, WS_POPUP, WS_SYSMENU, WS_THICKFRAME,
@@ -25,4 +26,10 @@ pub fn adjust_window(win: &fltk::window::Window) {
 
         SetActiveWindow(hwnd);
     }
+}
+
+pub fn attach_console() {
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,332 @@
+use crate::{download_engine::DownloadEngine, downloader, models::YggdrasilAccount};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const AUTHLIB_INJECTOR_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+const API_LOCATION_HEADER: &str = "X-Authlib-Injector-API-Location";
+pub(crate) const AUTHLIB_INJECTOR_DIR: &str = "moe/yushi/authlib-injector";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthResponse {
+    access_token: String,
+    client_token: String,
+    #[serde(default)]
+    selected_profile: Option<GameProfile>,
+    #[serde(default)]
+    available_profiles: Vec<GameProfile>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct GameProfile {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    #[serde(default)]
+    error: String,
+    #[serde(default, rename = "errorMessage")]
+    error_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InjectorArtifact {
+    version: String,
+    download_url: String,
+    checksums: InjectorChecksums,
+}
+
+#[derive(Debug, Deserialize)]
+struct InjectorChecksums {
+    sha256: String,
+}
+
+pub struct YggdrasilAuth {
+    server_url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl YggdrasilAuth {
+    pub fn new(server_url: &str) -> Self {
+        YggdrasilAuth {
+            server_url: server_url.trim_end_matches('/').to_string(),
+            client: DownloadEngine::shared().client().clone(),
+        }
+    }
+
+    pub fn authenticate(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<YggdrasilAccount, Box<dyn Error>> {
+        let server_url = resolve_api_location(&self.client, &self.server_url)?;
+
+        let response: AuthResponse = self.post(
+            &server_url,
+            "authenticate",
+            json!({
+                "agent": { "name": "Minecraft", "version": 1 },
+                "username": username,
+                "password": password,
+                "requestUser": true,
+            }),
+        )?;
+
+        let account = YggdrasilAccount {
+            server_url,
+            uuid: String::new(),
+            name: String::new(),
+            access_token: response.access_token,
+            client_token: response.client_token,
+        };
+
+        match response.selected_profile {
+            Some(profile) => Ok(with_profile(account, profile)),
+            None => {
+                let profile = response
+                    .available_profiles
+                    .into_iter()
+                    .next()
+                    .ok_or("This account has no game profiles")?;
+                self.refresh_with_profile(&account, Some(profile))
+            }
+        }
+    }
+
+    pub fn refresh(&self, account: &YggdrasilAccount) -> Result<YggdrasilAccount, Box<dyn Error>> {
+        self.refresh_with_profile(account, None)
+    }
+
+    pub fn ensure_valid(
+        &self,
+        account: &YggdrasilAccount,
+    ) -> Result<YggdrasilAccount, Box<dyn Error>> {
+        let response = self
+            .client
+            .post(format!("{}/authserver/validate", account.server_url))
+            .json(&json!({
+                "accessToken": account.access_token,
+                "clientToken": account.client_token,
+            }))
+            .send()?;
+
+        if response.status().is_success() {
+            Ok(account.clone())
+        } else {
+            self.refresh(account)
+        }
+    }
+
+    fn refresh_with_profile(
+        &self,
+        account: &YggdrasilAccount,
+        profile: Option<GameProfile>,
+    ) -> Result<YggdrasilAccount, Box<dyn Error>> {
+        let mut body = json!({
+            "accessToken": account.access_token,
+            "clientToken": account.client_token,
+            "requestUser": true,
+        });
+        if let Some(profile) = &profile {
+            body["selectedProfile"] = json!({ "id": profile.id, "name": profile.name });
+        }
+
+        let response: AuthResponse = self.post(&account.server_url, "refresh", body)?;
+        let profile = response
+            .selected_profile
+            .or(profile)
+            .unwrap_or_else(|| GameProfile {
+                id: account.uuid.clone(),
+                name: account.name.clone(),
+            });
+
+        Ok(with_profile(
+            YggdrasilAccount {
+                server_url: account.server_url.clone(),
+                uuid: String::new(),
+                name: String::new(),
+                access_token: response.access_token,
+                client_token: response.client_token,
+            },
+            profile,
+        ))
+    }
+
+    fn post(
+        &self,
+        server_url: &str,
+        endpoint: &str,
+        body: serde_json::Value,
+    ) -> Result<AuthResponse, Box<dyn Error>> {
+        let response = self
+            .client
+            .post(format!("{}/authserver/{}", server_url, endpoint))
+            .json(&body)
+            .send()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = match response.json::<ErrorResponse>() {
+                Ok(error) => error.error_message.unwrap_or(error.error),
+                Err(_) => status.to_string(),
+            };
+            return Err(format!("Auth server rejected the login: {}", message).into());
+        }
+
+        Ok(response.json()?)
+    }
+}
+
+fn with_profile(account: YggdrasilAccount, profile: GameProfile) -> YggdrasilAccount {
+    YggdrasilAccount {
+        uuid: profile.id,
+        name: profile.name,
+        ..account
+    }
+}
+
+fn resolve_api_location(
+    client: &reqwest::blocking::Client,
+    server_url: &str,
+) -> Result<String, Box<dyn Error>> {
+    let response = client.get(server_url).send()?;
+
+    let location = response
+        .headers()
+        .get(API_LOCATION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|location| response.url().join(location))
+        .transpose()?;
+
+    Ok(location
+        .map(|url| url.to_string())
+        .unwrap_or_else(|| server_url.to_string())
+        .trim_end_matches('/')
+        .to_string())
+}
+
+pub fn injector_arguments(
+    server_url: &str,
+    libraries_dir: &Path,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let client = DownloadEngine::shared().client();
+    let injector_dir = libraries_dir.join(AUTHLIB_INJECTOR_DIR);
+
+    let agent_path = match download_authlib_injector(client, &injector_dir) {
+        Ok(agent_path) => agent_path,
+        Err(e) => cached_authlib_injector(&injector_dir).ok_or_else(|| {
+            format!(
+                "authlib-injector could not be downloaded and no cached copy exists: {}",
+                e
+            )
+        })?,
+    };
+
+    let metadata_path = injector_dir.join("metadata").join(format!(
+        "{}.json",
+        downloader::to_hex(&Sha256::digest(server_url.as_bytes()))
+    ));
+    let metadata = match fetch_metadata(client, server_url) {
+        Ok(metadata) => {
+            if let Some(parent) = metadata_path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(&metadata_path, &metadata);
+            Some(metadata)
+        }
+        Err(_) => fs::read(&metadata_path).ok(),
+    };
+
+    let mut arguments = vec![format!(
+        "-javaagent:{}={}",
+        agent_path.to_string_lossy(),
+        server_url
+    )];
+    if let Some(metadata) = metadata {
+        arguments.push(format!(
+            "-Dauthlibinjector.yggdrasil.prefetched={}",
+            STANDARD.encode(&metadata)
+        ));
+    }
+    Ok(arguments)
+}
+
+fn fetch_metadata(
+    client: &reqwest::blocking::Client,
+    server_url: &str,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(client
+        .get(server_url)
+        .send()?
+        .error_for_status()?
+        .bytes()?
+        .to_vec())
+}
+
+fn download_authlib_injector(
+    client: &reqwest::blocking::Client,
+    injector_dir: &Path,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let artifact_url =
+        std::env::var("AUTHLIB_INJECTOR_URL").unwrap_or_else(|_| AUTHLIB_INJECTOR_URL.to_string());
+    let artifact: InjectorArtifact = client
+        .get(&artifact_url)
+        .send()?
+        .error_for_status()?
+        .json()?;
+
+    let agent_path = injector_dir
+        .join(&artifact.version)
+        .join(format!("authlib-injector-{}.jar", artifact.version));
+
+    if agent_path.exists()
+        && file_sha256(&agent_path)?.eq_ignore_ascii_case(&artifact.checksums.sha256)
+    {
+        return Ok(agent_path);
+    }
+
+    let _ = fs::remove_file(&agent_path);
+    downloader::download_file(&artifact.download_url, &agent_path, None, None)?;
+
+    let actual_sha256 = file_sha256(&agent_path)?;
+    if !actual_sha256.eq_ignore_ascii_case(&artifact.checksums.sha256) {
+        let _ = fs::remove_file(&agent_path);
+        return Err(format!(
+            "SHA-256 mismatch for authlib-injector: expected {}, got {}",
+            artifact.checksums.sha256, actual_sha256
+        )
+        .into());
+    }
+
+    Ok(agent_path)
+}
+
+fn cached_authlib_injector(injector_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(injector_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let version = entry.file_name().to_string_lossy().to_string();
+            let agent_path = entry
+                .path()
+                .join(format!("authlib-injector-{}.jar", version));
+            let modified = fs::metadata(&agent_path).ok()?.modified().ok()?;
+            Some((modified, agent_path))
+        })
+        .max()
+        .map(|(_, agent_path)| agent_path)
+}
+
+fn file_sha256(path: &Path) -> Result<String, Box<dyn Error>> {
+    let bytes = fs::read(path)?;
+    Ok(downloader::to_hex(&Sha256::digest(&bytes)))
+}
\ No newline at end of file